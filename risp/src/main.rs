@@ -4,10 +4,10 @@ mod risp_lineread_validator;
 
 use crate::risp_lineread_validator::RispValidator;
 use risp_lib::environment::RispEnv;
-use risp_lib::eval::eval;
-use risp_lib::parser::parse;
-use risp_lib::tokenizer::tokenize;
-use rustyline;
+use risp_lib::error::RispResult;
+use risp_lib::eval::eval_program;
+use risp_lib::parser::parse_program;
+use risp_lib::tokenizer::tokenize_with_positions;
 use rustyline::error::ReadlineError;
 use rustyline::{Editor, Helper};
 
@@ -20,10 +20,11 @@ fn main() {
     loop {
         let readline = rl.readline("lisp> ");
         match readline {
-            Ok(input) => match ep(input, env) {
-                Err(e) => eprintln!("{}", e),
-                _ => {}
-            },
+            Ok(input) => {
+                if let Err(e) = ep(input, env) {
+                    eprintln!("{}", e)
+                }
+            }
             Err(ReadlineError::Eof) => handle_exit(&mut rl),
             Err(e) => eprintln!("Error reading input: {}", e),
         }
@@ -48,10 +49,10 @@ fn setup_history(rl: &mut Editor<impl Helper>) {
     }
 }
 
-fn ep(input: String, env: &mut RispEnv) -> anyhow::Result<()> {
-    let token_stream = tokenize(&input)?;
-    let exp = parse(&token_stream)?;
-    println!("{}", eval(&exp, env)?);
+fn ep(input: String, env: &mut RispEnv) -> RispResult<()> {
+    let (token_stream, positions) = tokenize_with_positions(&input)?;
+    let forms = parse_program(&token_stream, &positions)?;
+    println!("{}", eval_program(&forms, env)?);
     Ok(())
 }
 
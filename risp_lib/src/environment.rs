@@ -1,39 +1,274 @@
 use crate::error::{RispError, RispResult};
-use crate::parser::RispExp;
+use crate::parser::{RispBuiltinFunction, RispExp, RispFunction};
 
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::path::PathBuf;
+use std::rc::{Rc, Weak};
 
-#[derive(Clone, Debug, Default)]
-pub struct RispEnv<'a> {
-    data: HashMap<String, RispExp>,
-    outer: Option<&'a RispEnv<'a>>,
+/// The namespace a fresh env starts in, like the repl.
+pub const DEFAULT_NAMESPACE: &str = "user";
+
+const INTERPRETER_DROPPED: &str =
+    "an env is used after every env owning its interpreter is dropped";
+
+/// A scope in the environment chain.
+///
+/// Cloning a `RispEnv` is cheap and the clone shares its bindings with the original, this is how
+/// lambdas capture the environment they were created in and keep it alive after the outer call returns.
+///
+/// The env made by [RispEnv::default] owns the interpreter, its namespaces and loaded modules, and
+/// so do its clones and the scopes made from them. An env kept inside the interpreter, as the
+/// enclosing scope of another one, by a lambda or by the registry of namespaces, doesn't, otherwise
+/// the registry would hold itself and never be freed.
+#[derive(Clone)]
+pub struct RispEnv {
+    data: Rc<RefCell<HashMap<String, RispExp>>>,
+    outer: Option<Rc<RispEnv>>,
+    namespace: Rc<Namespace>,
+    owned_registry: Option<Rc<NamespaceRegistry>>,
 }
 
-impl<'a> RispEnv<'a> {
-    pub fn with_outer<'b>(outer: &'b RispEnv) -> RispEnv<'b> {
+/// The name and require aliases of the namespace a chain of scopes belongs to.
+struct Namespace {
+    name: String,
+    aliases: RefCell<HashMap<String, String>>,
+    /// Weak since the registry holds the namespace's own env, it is kept alive by the envs that own
+    /// the interpreter.
+    registry: Weak<NamespaceRegistry>,
+}
+
+/// Every namespace loaded by one interpreter, keyed by name, and where to look for module files.
+struct NamespaceRegistry {
+    loaded: RefCell<HashMap<String, RispEnv>>,
+    search_path: RefCell<Vec<PathBuf>>,
+    /// The library functions by name, every namespace sees them behind its own bindings.
+    library: HashMap<String, RispExp>,
+}
+
+impl Default for RispEnv {
+    fn default() -> Self {
+        let registry = Rc::new(NamespaceRegistry {
+            loaded: Default::default(),
+            search_path: RefCell::new(vec![PathBuf::from(".")]),
+            library: RispBuiltinFunction::LIBRARY
+                .iter()
+                .map(|builtin| {
+                    let f = RispFunction::Builtin(builtin.clone());
+                    (f.to_string(), RispExp::Func(f))
+                })
+                .collect(),
+        });
+        let env = RispEnv::root(DEFAULT_NAMESPACE, &registry);
+        registry
+            .loaded
+            .borrow_mut()
+            .insert(DEFAULT_NAMESPACE.to_owned(), env.clone());
+        RispEnv {
+            owned_registry: Some(registry),
+            ..env
+        }
+    }
+}
+
+impl RispEnv {
+    fn root(name: &str, registry: &Rc<NamespaceRegistry>) -> RispEnv {
+        RispEnv {
+            data: Default::default(),
+            outer: None,
+            namespace: Rc::new(Namespace {
+                name: name.to_owned(),
+                aliases: Default::default(),
+                registry: Rc::downgrade(registry),
+            }),
+            owned_registry: None,
+        }
+    }
+
+    pub fn with_outer(outer: &RispEnv) -> RispEnv {
         RispEnv {
             data: Default::default(),
-            outer: Some(outer),
+            outer: Some(Rc::new(outer.captured())),
+            namespace: outer.namespace.clone(),
+            owned_registry: outer.owned_registry.clone(),
         }
     }
 
-    pub fn def(&mut self, name: &str, exp: &RispExp) -> RispResult<RispExp> {
-        self.data.insert(name.to_owned(), exp.clone());
+    /// A clone of this env for keeping inside the interpreter, eg by a lambda, that doesn't own it.
+    pub(crate) fn captured(&self) -> RispEnv {
+        RispEnv {
+            owned_registry: None,
+            ..self.clone()
+        }
+    }
+
+    /// env, owning the interpreter if this env does.
+    fn owned_like_self(&self, env: RispEnv) -> RispEnv {
+        RispEnv {
+            owned_registry: self.owned_registry.clone(),
+            ..env
+        }
+    }
+
+    fn registry(&self) -> Option<Rc<NamespaceRegistry>> {
+        self.namespace.registry.upgrade()
+    }
+
+    pub fn def(&self, name: &str, exp: &RispExp) -> RispResult<RispExp> {
+        self.data.borrow_mut().insert(name.to_owned(), exp.clone());
         Ok(RispExp::Nil)
     }
 
+    /// Rebinds the nearest existing binding of name, looking through the enclosing scopes.
+    pub fn set(&self, name: &str, exp: &RispExp) -> RispResult<RispExp> {
+        if let Some(binding) = self.data.borrow_mut().get_mut(name) {
+            *binding = exp.clone();
+            return Ok(exp.clone());
+        }
+        match &self.outer {
+            Some(outer) => outer.set(name, exp),
+            None => Err(RispError::UnexpectedSymbol(name.to_owned())),
+        }
+    }
+
     pub fn has_interned_var(&self, name: &str) -> bool {
-        self.data.contains_key(name)
+        self.data.borrow().contains_key(name)
+    }
+
+    /// Every binding in this scope, not the enclosing ones.
+    pub fn bindings(&self) -> Vec<(String, RispExp)> {
+        self.data
+            .borrow()
+            .iter()
+            .map(|(name, exp)| (name.clone(), exp.clone()))
+            .collect()
     }
 
-    pub fn get(&self, name: &str) -> RispResult<&RispExp> {
-        match self.data.get(name) {
-            Some(r) => Ok(r),
-            None => self
-                .outer
-                .map_or(Err(RispError::UnexpectedSymbol(name.to_owned())), |outer| {
-                    outer.get(name)
-                }),
+    /// Looks name up through the enclosing scopes and the library functions, then as a namespace
+    /// qualified alias/name.
+    pub fn get(&self, name: &str) -> RispResult<RispExp> {
+        self.get_local(name)
+            .or_else(|| self.get_qualified(name))
+            .ok_or_else(|| RispError::UnexpectedSymbol(name.to_owned()))
+    }
+
+    fn get_local(&self, name: &str) -> Option<RispExp> {
+        match (self.data.borrow().get(name), &self.outer) {
+            (Some(r), _) => Some(r.clone()),
+            (None, Some(outer)) => outer.get_local(name),
+            (None, None) => self.registry()?.library.get(name).cloned(),
+        }
+    }
+
+    fn get_qualified(&self, name: &str) -> Option<RispExp> {
+        let (namespace, name) = name
+            .split_once('/')
+            .filter(|(ns, n)| !ns.is_empty() && !n.is_empty())?;
+        let namespace = self
+            .namespace
+            .aliases
+            .borrow()
+            .get(namespace)
+            .cloned()
+            .unwrap_or_else(|| namespace.to_owned());
+        self.namespace(&namespace)?.data.borrow().get(name).cloned()
+    }
+
+    /// The name of the namespace this scope belongs to.
+    pub fn namespace_name(&self) -> &str {
+        &self.namespace.name
+    }
+
+    /// The root env of a loaded namespace.
+    pub fn namespace(&self, name: &str) -> Option<RispEnv> {
+        let namespace = self.registry()?.loaded.borrow().get(name).cloned()?;
+        Some(self.owned_like_self(namespace))
+    }
+
+    /// The root env of namespace name, it is created empty if it hasn't been loaded yet.
+    pub fn namespace_or_create(&self, name: &str) -> RispEnv {
+        let registry = self.registry().expect(INTERPRETER_DROPPED);
+        let namespace = registry
+            .loaded
+            .borrow_mut()
+            .entry(name.to_owned())
+            .or_insert_with(|| RispEnv::root(name, &registry))
+            .clone();
+        self.owned_like_self(namespace)
+    }
+
+    /// Forgets a loaded namespace, so the next require of it loads it again.
+    pub fn remove_namespace(&self, name: &str) {
+        if let Some(registry) = self.registry() {
+            registry.loaded.borrow_mut().remove(name);
+        }
+    }
+
+    /// Makes alias/name in this namespace refer to name in the namespace target.
+    pub fn alias_namespace(&self, alias: &str, target: &str) {
+        self.namespace
+            .aliases
+            .borrow_mut()
+            .insert(alias.to_owned(), target.to_owned());
+    }
+
+    /// The directories module files are searched for in, in order. The default is just ".".
+    pub fn search_path(&self) -> Vec<PathBuf> {
+        self.registry()
+            .map(|registry| registry.search_path.borrow().clone())
+            .unwrap_or_default()
+    }
+
+    pub fn set_search_path(&self, search_path: Vec<PathBuf>) {
+        if let Some(registry) = self.registry() {
+            registry.search_path.replace(search_path);
+        }
+    }
+}
+
+/// Two envs are equal when they are the same scope, not when they happen to hold the same bindings.
+impl PartialEq for RispEnv {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.data, &other.data)
+    }
+}
+
+/// The registry is left out since it holds this env's namespace, printing it would never end.
+impl Debug for RispEnv {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RispEnv")
+            .field("namespace", &self.namespace.name)
+            .field("data", &self.data)
+            .field("outer", &self.outer)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::environment::RispEnv;
+    use crate::eval::eval;
+    use crate::parser::parse;
+    use crate::tokenizer::tokenize;
+
+    #[test]
+    fn dropping_the_env_frees_its_namespaces() {
+        let mut env = RispEnv::default();
+        let registry = Rc::downgrade(env.owned_registry.as_ref().unwrap());
+        for input in [
+            "(def f (fn (x) (first x)))",
+            "(ns other)",
+            "(def g (fn () f))",
+        ] {
+            eval(&parse(&tokenize(input).unwrap()).unwrap(), &mut env).unwrap();
         }
+        assert_eq!(env.namespace_name(), "other");
+        assert!(registry.upgrade().is_some());
+
+        drop(env);
+        assert!(registry.upgrade().is_none());
     }
 }
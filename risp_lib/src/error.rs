@@ -1,5 +1,6 @@
+use crate::eval::accepted_arities;
 use crate::parser::{RispExp, RispFunction};
-use crate::tokenizer::RispToken;
+use crate::tokenizer::{Position, RispToken};
 use std::num::{ParseFloatError, ParseIntError};
 use std::str::ParseBoolError;
 use thiserror::Error;
@@ -19,6 +20,8 @@ pub enum RispError {
 
     #[error("The input {0} is not recognized as any valid token")]
     UnrecognizedToken(String),
+    #[error("The regex #\"{0}\" is invalid: {1}")]
+    InvalidRegex(String, String),
     #[error("The token {0:?} was not expected here {1}")]
     UnexpectedToken(RispToken, String),
 
@@ -29,9 +32,29 @@ pub enum RispError {
 
     #[error("Def must be of the form (def symbol expr)")]
     MalformedDefExpression,
+    #[error("Set! must be of the form (set! symbol expr)")]
+    MalformedSetExpression,
+    #[error("Defmacro must be of the form (defmacro symbol (params) body...)")]
+    MalformedDefmacroExpression,
+    #[error("{0} must be of the form ({0} (symbol expr ...) body...)")]
+    MalformedBindingForm(String),
+
+    #[error("{0} must be given pairs of clauses")]
+    MalformedClauses(String),
+    #[error("No clause of {0} matched {1}")]
+    NoMatchingClause(String, RispExp),
+
+    #[error("The value {1} does not match the pattern {0}")]
+    PatternMismatch(RispExp, RispExp),
 
     #[error("Primitive type mismatch: {0}")]
     TypeError(&'static str),
+    #[error("Division by zero")]
+    DivisionByZero,
+    #[error("Index {0} is out of bounds for a sequence of length {1}")]
+    IndexOutOfBounds(i64, usize),
+    #[error("The start {0} is after the end {1}")]
+    StartAfterEnd(i64, i64),
 
     #[error("Error parsing integer: {0}")]
     ParseIntError(#[from] ParseIntError),
@@ -40,26 +63,122 @@ pub enum RispError {
     #[error("Error parsing bool: {0}")]
     ParseBoolError(#[from] ParseBoolError),
 
-    #[error("Arity mismatch caused by: {0:?}")]
+    #[error("Arity mismatch caused by: {0:?}{}", accepted_arities(.0))]
     ArityMismatch(RispFunction),
 
     #[error("The previous LParen was unterminated")]
     UnterminatedList,
     #[error("The previous Quote was unterminated")]
     UnterminatedString,
+    #[error("The escape sequence {0} is not valid in a string")]
+    InvalidEscape(String),
+
+    #[error("recur can only be used in the tail of a loop or fn")]
+    RecurOutsideTailPosition,
+    #[error("recur expected {0} args but got {1}")]
+    RecurArityMismatch(usize, usize),
 
     #[error("{0:?} is not an evaluable function")]
     FirstFormMustBeFunction(RispExp),
+
+    #[error("{0} can only be used inside a quasiquote")]
+    UnquoteOutsideQuasiquote(String),
+
+    #[error("Ns must be of the form (ns symbol)")]
+    MalformedNsExpression,
+    #[error("Require must be of the form (require module :as alias :refer (symbol...))")]
+    MalformedRequireExpression,
+    #[error("Could not find the module {0} on the search path")]
+    ModuleNotFound(String),
+    #[error("Error loading {0}: {1}")]
+    ModuleLoadError(String, String),
+
+    #[error("Error in the top level form at {0}: {1}")]
+    InForm(Position, Box<RispError>),
+    #[error("Error in the token at {0}: {1}")]
+    InToken(Position, Box<RispError>),
+
+    #[error("Uncaught exception: {0} {1}")]
+    Thrown(String, RispExp),
+    #[error(
+        "Try must be of the form (try body... (catch kind symbol body...)... (finally body...))"
+    )]
+    MalformedTryExpression,
+}
+
+impl RispError {
+    /// The name a (catch kind e ...) clause uses to catch this error.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RispError::InForm(_, e) | RispError::InToken(_, e) => e.kind(),
+            RispError::GenericError(_) => "generic-error",
+            RispError::UnexpectedEndOfInput => "unexpected-end-of-input",
+            RispError::InvalidName(_) => "invalid-name",
+            RispError::UnrecognizedToken(_) => "unrecognized-token",
+            RispError::InvalidRegex(_, _) => "invalid-regex",
+            RispError::UnexpectedToken(_, _) => "unexpected-token",
+            RispError::UnexpectedExpr(_, _) => "unexpected-expr",
+            RispError::UnexpectedSymbol(_) => "unexpected-symbol",
+            RispError::MalformedDefExpression => "malformed-def",
+            RispError::MalformedSetExpression => "malformed-set",
+            RispError::MalformedDefmacroExpression => "malformed-defmacro",
+            RispError::MalformedBindingForm(_) => "malformed-binding-form",
+            RispError::MalformedClauses(_) => "malformed-clauses",
+            RispError::NoMatchingClause(_, _) => "no-matching-clause",
+            RispError::PatternMismatch(_, _) => "pattern-mismatch",
+            RispError::TypeError(_) => "type-error",
+            RispError::DivisionByZero => "division-by-zero",
+            RispError::IndexOutOfBounds(_, _) => "index-out-of-bounds",
+            RispError::StartAfterEnd(_, _) => "start-after-end",
+            RispError::ParseIntError(_) => "parse-int-error",
+            RispError::ParseFloatError(_) => "parse-float-error",
+            RispError::ParseBoolError(_) => "parse-bool-error",
+            RispError::ArityMismatch(_) => "arity-mismatch",
+            RispError::UnterminatedList => "unterminated-list",
+            RispError::UnterminatedString => "unterminated-string",
+            RispError::InvalidEscape(_) => "invalid-escape",
+            RispError::RecurOutsideTailPosition => "recur-outside-tail-position",
+            RispError::RecurArityMismatch(_, _) => "recur-arity-mismatch",
+            RispError::FirstFormMustBeFunction(_) => "first-form-must-be-function",
+            RispError::UnquoteOutsideQuasiquote(_) => "unquote-outside-quasiquote",
+            RispError::MalformedNsExpression => "malformed-ns",
+            RispError::MalformedRequireExpression => "malformed-require",
+            RispError::ModuleNotFound(_) => "module-not-found",
+            RispError::ModuleLoadError(_, _) => "module-load-error",
+            RispError::Thrown(_, _) => "thrown",
+            RispError::MalformedTryExpression => "malformed-try",
+        }
+    }
 }
 
 pub const ILLEGAL_TYPE_FOR_ARITHMETIC_OP: &str =
     "Attempting to do arithmetic operation on non float/int with builtin";
+pub const INCOMPARABLE_TYPES: &str =
+    "Attempting to order values that can't be compared, only numbers, strings and chars can be";
+pub const KEYWORD_CALL_ARGS: &str =
+    "a keyword is called with the collection to look itself up in and an optional default";
+pub const EXPECTED_SEQUENCE: &str = "expected a list, vector, map, set, string or nil";
+pub const EXPECTED_INTEGER: &str = "expected an integer";
+pub const EXPECTED_STRING: &str = "expected a string";
+pub const EXPECTED_REGEX: &str = "expected a regex";
+pub const RANGE_STEP_IS_ZERO: &str = "a range step of 0 would never reach the end";
+pub const EXPECTED_ATOM: &str = "expected an atom";
+pub const MALFORMED_PARAMS: &str =
+    "params must be symbols, then &opt followed by symbols or (symbol default) pairs, then & and a single symbol, or else &key followed by symbols or (symbol default) pairs";
+pub const MALFORMED_FN_CLAUSE: &str =
+    "a multi-arity fn is made of ([params] body...) clauses, single-arity params that start with a vector pattern can be written as a vector, eg (fn [[a b] c] ...)";
+pub const UNKNOWN_KEYWORD_ARG: &str = "expected a keyword naming one of the fn's &key params";
+pub const MALFORMED_PATTERN: &str =
+    "patterns must be symbols, lists or vectors of patterns with & and a single pattern at the end, or maps of patterns to keys";
+pub const UNQUOTE_SPLICING_EXPECTS_LIST: &str =
+    "unquote-splicing can only splice a list into place";
 
 // Unexpected token
 pub const TRAILING_TOKENS: &str =
     "Trailing tokens, did you type extra stuff outside parens? This is a lisp you know...";
 pub const UNEXPECTED_CLOSING_PAREN: &str =
     "A closing paren was not expected here, are there too many?";
+pub const MAP_LITERAL_NEEDS_PAIRS: &str = "A map literal must have a value for every key";
 pub const EXPECTED_ARGS_LIST_FOR_FN: &str = "A fn must have an args list in it's first argument";
 pub const EXPECTED_FN_DEF_FOR_FN: &str =
-    "A fn must have a function definition in it's second argument";
+    "A fn must have at least one body form after its args list";
@@ -1,44 +1,75 @@
+/// Folds numeric args with $op in the widest kind among them: float, then ratio, then integer.
+/// Integers are folded as i64 with $checked_int_op and redone as bignums if that overflows.
 #[macro_export]
 macro_rules! number_list_apply {
-    ($args:ident, $method:expr) => {{
-        let t = check_for_illegal_arithmetic_input($args);
-        if (t.is_err()) {
-            Err(t.unwrap_err())
+    ($args:ident, $identity:expr, $op:expr, $checked_int_op:expr) => {{
+        if let Err(e) = check_for_illegal_arithmetic_input($args) {
+            Err(e)
+        } else if $args.iter().any(|arg| matches!(arg, RispExp::Float(_))) {
+            Ok(RispExp::Float(
+                $args.iter().map(expr_to_float).fold($identity as f64, $op),
+            ))
+        } else if $args.iter().any(|arg| matches!(arg, RispExp::Ratio(_))) {
+            Ok($args
+                .iter()
+                .map(expr_to_ratio)
+                .fold(BigRational::from_integer($identity.into()), $op)
+                .into())
         } else {
-            if $args.iter().any(|arg| matches!(arg, RispExp::Float(_))) {
-                Ok(RispExp::Float($method(
-                    $args.iter().map(|arg| expr_to_float(arg)),
-                )))
-            } else {
-                Ok(RispExp::Integer($method($args.iter().map(expr_to_int))))
-            }
+            Ok(
+                match $args.iter().try_fold($identity, |acc, arg| match arg {
+                    RispExp::Integer(i) => $checked_int_op(acc, *i),
+                    _ => None,
+                }) {
+                    Some(i) => RispExp::Integer(i),
+                    None => $args
+                        .iter()
+                        .map(expr_to_bigint)
+                        .fold(BigInt::from($identity), $op)
+                        .into(),
+                },
+            )
         }
     }};
 }
+/// Like number_list_apply but folds the rest of the args into the first instead of an identity.
 #[macro_export]
 macro_rules! number_list_subtractive_apply {
-    ($args:ident, $default:expr, $operation:expr, $others_combiner:expr) => {{
-        let t = check_for_illegal_arithmetic_input($args);
-        if (t.is_err()) {
-            Err(t.unwrap_err())
-        } else {
-            let first = $args.first();
-
-            if first.is_none() {
-                Ok($default)
+    ($args:ident, $default:expr, $op:expr, $checked_int_op:expr) => {{
+        if let Err(e) = check_for_illegal_arithmetic_input($args) {
+            Err(e)
+        } else if let Some((first, rest)) = $args.split_first() {
+            if $args.iter().any(|arg| matches!(arg, RispExp::Float(_))) {
+                Ok(RispExp::Float(
+                    rest.iter()
+                        .map(expr_to_float)
+                        .fold(expr_to_float(first), $op),
+                ))
+            } else if $args.iter().any(|arg| matches!(arg, RispExp::Ratio(_))) {
+                Ok(rest
+                    .iter()
+                    .map(expr_to_ratio)
+                    .fold(expr_to_ratio(first), $op)
+                    .into())
             } else {
-                let first = first.unwrap();
-                let rest = &$args[1..];
-                if $args.iter().any(|arg| matches!(arg, RispExp::Float(_))) {
-                    let first = expr_to_float(first);
-                    let sub: f64 = $others_combiner(rest.iter().map(expr_to_float));
-                    Ok(RispExp::Float($operation(first, sub)))
-                } else {
-                    let first = expr_to_int(first);
-                    let sub: i32 = $others_combiner(rest.iter().map(expr_to_int));
-                    Ok(RispExp::Integer($operation(first, sub)))
-                }
+                let checked = match first {
+                    RispExp::Integer(first) => rest.iter().try_fold(*first, |acc, arg| match arg {
+                        RispExp::Integer(i) => $checked_int_op(acc, *i),
+                        _ => None,
+                    }),
+                    _ => None,
+                };
+                Ok(match checked {
+                    Some(i) => RispExp::Integer(i),
+                    None => rest
+                        .iter()
+                        .map(expr_to_bigint)
+                        .fold(expr_to_bigint(first), $op)
+                        .into(),
+                })
             }
+        } else {
+            Ok($default)
         }
     }};
 }
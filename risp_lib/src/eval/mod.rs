@@ -1,23 +1,213 @@
 use crate::environment::RispEnv;
-use crate::error::{RispError, RispResult, ILLEGAL_TYPE_FOR_ARITHMETIC_OP};
-use crate::parser::{RispBuiltinFunction, RispExp, RispFunction};
+use crate::error::{
+    RispError, RispResult, EXPECTED_ATOM, EXPECTED_INTEGER, EXPECTED_STRING,
+    ILLEGAL_TYPE_FOR_ARITHMETIC_OP, INCOMPARABLE_TYPES, KEYWORD_CALL_ARGS, MALFORMED_PARAMS,
+    MALFORMED_PATTERN, UNKNOWN_KEYWORD_ARG, UNQUOTE_SPLICING_EXPECTS_LIST,
+};
+use crate::parser::{
+    parse_program, FnClause, RispAtom, RispBuiltinFunction, RispExp, RispFunction,
+};
+use crate::symbols_constants::{
+    CATCH_SYM, FINALLY_SYM, KEY_PARAM_SYM, OPTIONAL_PARAM_SYM, REST_PARAM_SYM, WILDCARD_SYM,
+};
+use crate::tokenizer::{tokenize_with_positions, Position};
 use crate::{number_list_apply, number_list_subtractive_apply};
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::Zero;
+use std::cmp::Ordering;
+use std::fs;
+use std::ops::Div;
+use std::path::Path;
+use std::rc::Rc;
 
 mod macros;
+mod namespaces;
+mod regexes;
+mod sequences;
+mod strings;
+
+use namespaces::{op_ns, op_require};
+use regexes::{op_re_find, op_re_matches, op_re_replace, op_re_seq};
+use sequences::{
+    op_concat, op_cons, op_count, op_filter, op_first, op_is_empty, op_map, op_nth, op_range,
+    op_reduce, op_rest, op_reverse,
+};
+use strings::{
+    op_index_of, op_join, op_lower, op_replace, op_split, op_starts_with, op_str, op_str_len,
+    op_subs, op_trim, op_upper,
+};
 
 pub fn eval(exp: &RispExp, env: &mut RispEnv) -> RispResult<RispExp> {
+    run(eval_step(exp, env)?)
+}
+
+/// Evaluates top level forms in order and returns the value of the last one, or stops at the first
+/// error, wrapped in [RispError::InForm] with the position of the form that raised it.
+pub fn eval_program(forms: &[(RispExp, Position)], env: &mut RispEnv) -> RispResult<RispExp> {
+    let mut result = RispExp::Empty;
+    for (form, position) in forms {
+        result = eval(form, env).map_err(|e| RispError::InForm(*position, Box::new(e)))?;
+    }
+    Ok(result)
+}
+
+/// Reads, parses and evaluates the file at path in env, see [eval_program].
+pub fn load_file(path: impl AsRef<Path>, env: &mut RispEnv) -> RispResult<RispExp> {
+    let path = path.as_ref();
+    let source = fs::read_to_string(path)
+        .map_err(|e| RispError::ModuleLoadError(path.display().to_string(), e.to_string()))?;
+    let (tokens, positions) = tokenize_with_positions(&source)?;
+    eval_program(&parse_program(&tokens, &positions)?, env)
+}
+
+/// Keeps taking steps until one of them is done.
+///
+/// A recur can only jump back to a loop or fn entered by this same run, that is one it is in the
+/// tail of. Anything evaluated in a nested [eval] (args, if tests, all but the last form of a body)
+/// starts with no target, so a recur there is an error.
+fn run(mut step: Step) -> RispResult<RispExp> {
+    let mut recur_target = None;
+    loop {
+        step = match step {
+            Step::Done(result) => return Ok(result),
+            Step::TailCall(exp, mut env) => eval_step(&exp, &mut env)?,
+            Step::Enter(target, next) => {
+                recur_target = Some(target);
+                *next
+            }
+            Step::Recur(args) => match &recur_target {
+                Some(target) => target.recur(args)?,
+                None => return Err(RispError::RecurOutsideTailPosition),
+            },
+        }
+    }
+}
+
+/// The outcome of evaluating an expression by one step.
+///
+/// Forms in tail position (the branches of an if, the body of a lambda) are not evaluated
+/// recursively, they are handed back to [eval] as a [Step::TailCall] so it can keep going in a loop
+/// and tail recursive risp code runs in constant Rust stack. Lists share their items so handing a
+/// form on doesn't copy it.
+enum Step {
+    Done(RispExp),
+    TailCall(RispExp, RispEnv),
+    /// Entering a loop or fn body, which a recur in its tail jumps back to.
+    Enter(RecurTarget, Box<Step>),
+    /// A recur with its evaluated args.
+    Recur(Vec<RispExp>),
+}
+
+/// Where a recur jumps back to, the innermost loop or fn it is in the tail of.
+enum RecurTarget {
+    Loop {
+        patterns: Vec<RispExp>,
+        body: Rc<[RispExp]>,
+        env: RispEnv,
+    },
+    Fn {
+        f: RispFunction,
+        env: RispEnv,
+    },
+}
+
+impl RecurTarget {
+    fn recur(&self, args: Vec<RispExp>) -> RispResult<Step> {
+        match self {
+            RecurTarget::Loop {
+                patterns,
+                body,
+                env,
+            } => {
+                if args.len() != patterns.len() {
+                    return Err(RispError::RecurArityMismatch(patterns.len(), args.len()));
+                }
+                let scope = RispEnv::with_outer(env);
+                for (pattern, value) in patterns.iter().zip(&args) {
+                    bind_pattern(&scope, pattern, value)?;
+                }
+                eval_body(body, scope)
+            }
+            RecurTarget::Fn { f, env } => call_function(f, &args, &mut env.clone()),
+        }
+    }
+}
+
+fn eval_step(exp: &RispExp, env: &mut RispEnv) -> RispResult<Step> {
     match exp {
         RispExp::List(forms) => eval_list_as_func(forms, env),
-        RispExp::Symbol(s) if env.has_interned_var(&s) => {
-            Ok(env.get(&s).map(|x| x.clone()).unwrap())
+        RispExp::Symbol(s) => Ok(Step::Done(env.get(s).unwrap_or_else(|_| exp.clone()))),
+        RispExp::Vector(_) | RispExp::Map(_) | RispExp::Set(_) => {
+            eval_collection(exp, env).map(Step::Done)
         }
+        _ => Ok(Step::Done(exp.clone())),
+    }
+}
+
+/// Collection literals evaluate to the same kind of collection holding the values of their items,
+/// keys included.
+fn eval_collection(exp: &RispExp, env: &mut RispEnv) -> RispResult<RispExp> {
+    match exp {
+        RispExp::Vector(items) => Ok(RispExp::Vector(
+            items
+                .iter()
+                .map(|item| eval(item, env))
+                .collect::<RispResult<_>>()?,
+        )),
+        RispExp::Map(map) => Ok(RispExp::Map(Box::new(
+            map.iter()
+                .map(|(k, v)| Ok((eval(k, env)?, eval(v, env)?)))
+                .collect::<RispResult<_>>()?,
+        ))),
+        RispExp::Set(set) => Ok(RispExp::Set(Box::new(
+            set.iter()
+                .map(|item| eval(item, env))
+                .collect::<RispResult<_>>()?,
+        ))),
         _ => Ok(exp.clone()),
     }
 }
 
-fn eval_list_as_func(forms: &[RispExp], env: &mut RispEnv) -> RispResult<RispExp> {
-    if forms.len() < 1 {
-        return Ok(RispExp::List(vec![]));
+fn eval_list_as_func(forms: &[RispExp], env: &mut RispEnv) -> RispResult<Step> {
+    let (first, rest) = match forms.split_first() {
+        Some(split) => split,
+        None => return Ok(Step::Done(RispExp::List(Rc::new([])))),
+    };
+
+    // Macros are handed their arguments as code, whatever code they return is evaluated in their place.
+    if let Some(expansion) = macroexpand_1(forms, env)? {
+        return Ok(Step::TailCall(expansion, env.clone()));
+    }
+
+    // Special forms get their arguments unevaluated and decide for themselves what to evaluate.
+    if let RispExp::Func(f @ RispFunction::Builtin(builtin)) = first {
+        match builtin {
+            RispBuiltinFunction::If => return op_if(rest, f, env),
+            RispBuiltinFunction::And => return boolean_and(rest, env).map(Step::Done),
+            RispBuiltinFunction::Or => return boolean_or(rest, env).map(Step::Done),
+            RispBuiltinFunction::Quote => return op_quote(rest, f).map(Step::Done),
+            RispBuiltinFunction::Quasiquote => return op_quasiquote(rest, f, env).map(Step::Done),
+            RispBuiltinFunction::Unquote | RispBuiltinFunction::UnquoteSplicing => {
+                return Err(RispError::UnquoteOutsideQuasiquote(f.to_string()))
+            }
+            RispBuiltinFunction::Def => return op_def(env, rest, f).map(Step::Done),
+            RispBuiltinFunction::Defmacro => return op_defmacro(env, rest, f).map(Step::Done),
+            RispBuiltinFunction::Do => return eval_body(rest, env.clone()),
+            RispBuiltinFunction::Set => return op_set(env, rest, f).map(Step::Done),
+            RispBuiltinFunction::Try => return op_try(env, rest).map(Step::Done),
+            RispBuiltinFunction::Ns => return op_ns(env, rest, f).map(Step::Done),
+            RispBuiltinFunction::Require => return op_require(env, rest).map(Step::Done),
+            RispBuiltinFunction::Cond => return op_cond(env, rest, f),
+            RispBuiltinFunction::Case => return op_case(env, rest, f),
+            RispBuiltinFunction::Match => return op_match(env, rest, f),
+            RispBuiltinFunction::Let => return op_let(env, rest, f),
+            RispBuiltinFunction::LetStar => return op_let_star(env, rest, f),
+            RispBuiltinFunction::Letrec => return op_letrec(env, rest, f),
+            RispBuiltinFunction::Loop => return op_loop(env, rest, f),
+            RispBuiltinFunction::Recur => return op_recur(env, rest),
+            _ => {}
+        }
     }
 
     let evaluated = forms
@@ -26,137 +216,998 @@ fn eval_list_as_func(forms: &[RispExp], env: &mut RispEnv) -> RispResult<RispExp
         .collect::<RispResult<Vec<RispExp>>>()?;
     let (first, rest) = evaluated.split_first().unwrap();
     match first {
-        RispExp::Func(f) => match f {
-            RispFunction::Builtin(RispBuiltinFunction::Plus) => plus(&rest),
-            RispFunction::Builtin(RispBuiltinFunction::Minus) => minus(&rest),
-            RispFunction::Builtin(RispBuiltinFunction::Multiply) => multiply(&rest),
-            RispFunction::Builtin(RispBuiltinFunction::Divide) => divide(&rest),
+        RispExp::Func(f) => call_function(f, rest, env),
 
-            RispFunction::Builtin(RispBuiltinFunction::And) => boolean_and(&rest),
-            RispFunction::Builtin(RispBuiltinFunction::Xor) => boolean_xor(&rest),
-            RispFunction::Builtin(RispBuiltinFunction::Or) => boolean_or(&rest),
-            RispFunction::Builtin(RispBuiltinFunction::Not) => boolean_not(&rest),
+        // Convert a literal into an executable that closes over the current env.
+        RispExp::Lambda(RispFunction::Function { clauses, .. }) => {
+            close_over(clauses, env).map(|f| Step::Done(RispExp::Func(f)))
+        }
+        RispExp::Lambda(f) => Ok(Step::Done(RispExp::Func(f.clone()))),
 
-            RispFunction::Builtin(RispBuiltinFunction::LT) => op_lt(&rest),
-            RispFunction::Builtin(RispBuiltinFunction::LTE) => op_lte(&rest),
-            RispFunction::Builtin(RispBuiltinFunction::GT) => op_gt(&rest),
-            RispFunction::Builtin(RispBuiltinFunction::GTE) => op_gte(&rest),
-            RispFunction::Builtin(RispBuiltinFunction::EQ) => op_eq(&rest),
+        RispExp::Keyword(_) => keyword_lookup(first, rest).map(Step::Done),
 
-            RispFunction::Builtin(RispBuiltinFunction::Def) => op_def(env, rest, f),
+        _ => Err(RispError::FirstFormMustBeFunction(first.clone())),
+    }
+}
 
-            RispFunction::Builtin(RispBuiltinFunction::If) => op_if(rest, f),
+/// (:key coll default), a keyword called as a function looks itself up in coll and gives default,
+/// or nil, when it isn't there. Maps look up their keys and sets their items, anything else misses.
+fn keyword_lookup(keyword: &RispExp, args: &[RispExp]) -> RispResult<RispExp> {
+    let (coll, default) = match args {
+        [coll] => (coll, RispExp::Nil),
+        [coll, default] => (coll, default.clone()),
+        _ => Err(RispError::UnexpectedExpr(
+            keyword.clone(),
+            KEYWORD_CALL_ARGS.to_owned(),
+        ))?,
+    };
+    let found = match coll {
+        RispExp::Map(map) => map.get(keyword),
+        RispExp::Set(set) => set.get(keyword),
+        _ => None,
+    };
+    Ok(found.cloned().unwrap_or(default))
+}
 
-            f @ RispFunction::Function { params, body } => {
-                eval(body, &mut env_for_lambda(f, params, rest, env)?)
+/// Calls f with already evaluated args. The body of a lambda is in tail position so it is handed
+/// back as a [Step::TailCall] rather than evaluated here.
+fn call_function(f: &RispFunction, args: &[RispExp], env: &mut RispEnv) -> RispResult<Step> {
+    let result = match f {
+        RispFunction::Builtin(RispBuiltinFunction::Plus) => plus(args),
+        RispFunction::Builtin(RispBuiltinFunction::Minus) => minus(args),
+        RispFunction::Builtin(RispBuiltinFunction::Multiply) => multiply(args),
+        RispFunction::Builtin(RispBuiltinFunction::Divide) => divide(args),
+
+        RispFunction::Builtin(RispBuiltinFunction::Xor) => boolean_xor(args),
+        RispFunction::Builtin(RispBuiltinFunction::Not) => boolean_not(args),
+
+        RispFunction::Builtin(RispBuiltinFunction::LT) => op_lt(args),
+        RispFunction::Builtin(RispBuiltinFunction::LTE) => op_lte(args),
+        RispFunction::Builtin(RispBuiltinFunction::GT) => op_gt(args),
+        RispFunction::Builtin(RispBuiltinFunction::GTE) => op_gte(args),
+        RispFunction::Builtin(RispBuiltinFunction::EQ) => op_eq(args),
+
+        RispFunction::Builtin(RispBuiltinFunction::Atom) => op_atom(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::Deref) => op_deref(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::Reset) => op_reset(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::Swap) => op_swap(env, args, f),
+
+        RispFunction::Builtin(RispBuiltinFunction::Throw) => op_throw(args, f),
+
+        RispFunction::Builtin(RispBuiltinFunction::Cons) => op_cons(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::First) => op_first(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::Rest) => op_rest(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::Nth) => op_nth(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::Count) => op_count(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::IsEmpty) => op_is_empty(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::Map) => op_map(env, args, f),
+        RispFunction::Builtin(RispBuiltinFunction::Filter) => op_filter(env, args, f),
+        RispFunction::Builtin(RispBuiltinFunction::Reduce) => op_reduce(env, args, f),
+        RispFunction::Builtin(RispBuiltinFunction::Concat) => op_concat(args),
+        RispFunction::Builtin(RispBuiltinFunction::Reverse) => op_reverse(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::Range) => op_range(args, f),
+
+        RispFunction::Builtin(RispBuiltinFunction::Str) => op_str(args),
+        RispFunction::Builtin(RispBuiltinFunction::Subs) => op_subs(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::StrLen) => op_str_len(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::Upper) => op_upper(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::Lower) => op_lower(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::Trim) => op_trim(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::Split) => op_split(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::Join) => op_join(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::StartsWith) => op_starts_with(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::IndexOf) => op_index_of(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::Replace) => op_replace(args, f),
+
+        RispFunction::Builtin(RispBuiltinFunction::ReFind) => op_re_find(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::ReMatches) => op_re_matches(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::ReSeq) => op_re_seq(args, f),
+        RispFunction::Builtin(RispBuiltinFunction::ReReplace) => op_re_replace(args, f),
+
+        RispFunction::Builtin(RispBuiltinFunction::Macroexpand1) => op_macroexpand_1(env, args, f),
+        RispFunction::Builtin(RispBuiltinFunction::Macroexpand) => op_macroexpand(env, args, f),
+
+        // Special forms only work in call position, they can't be passed around as values.
+        RispFunction::Builtin(
+            RispBuiltinFunction::If
+            | RispBuiltinFunction::And
+            | RispBuiltinFunction::Or
+            | RispBuiltinFunction::Quote
+            | RispBuiltinFunction::Quasiquote
+            | RispBuiltinFunction::Unquote
+            | RispBuiltinFunction::UnquoteSplicing
+            | RispBuiltinFunction::Def
+            | RispBuiltinFunction::Defmacro
+            | RispBuiltinFunction::Do
+            | RispBuiltinFunction::Set
+            | RispBuiltinFunction::Try
+            | RispBuiltinFunction::Ns
+            | RispBuiltinFunction::Require
+            | RispBuiltinFunction::Cond
+            | RispBuiltinFunction::Case
+            | RispBuiltinFunction::Match
+            | RispBuiltinFunction::Let
+            | RispBuiltinFunction::LetStar
+            | RispBuiltinFunction::Letrec
+            | RispBuiltinFunction::Loop
+            | RispBuiltinFunction::Recur,
+        ) => Err(RispError::FirstFormMustBeFunction(RispExp::Func(f.clone()))),
+
+        RispFunction::Function {
+            clauses,
+            env: closure_env,
+        } => {
+            let parent = closure_env.as_ref().unwrap_or(env);
+            for FnClause { body, spec, .. } in clauses.iter() {
+                let spec = spec.as_ref().expect("functions are made by close_over");
+                if spec.accepts(args.len()) {
+                    let call_env = env_for_lambda(spec, args, parent)?;
+                    let target = RecurTarget::Fn {
+                        f: f.clone(),
+                        env: env.clone(),
+                    };
+                    let body = Step::TailCall(RispExp::clone(body), call_env);
+                    return Ok(Step::Enter(target, Box::new(body)));
+                }
             }
+            Err(RispError::ArityMismatch(f.clone()))
+        }
+    };
+    result.map(Step::Done)
+}
+
+/// Makes a function of the clauses of a fn literal that closes over env. The params of each clause
+/// are parsed here, once, rather than on every call.
+fn close_over(clauses: &[FnClause], env: &RispEnv) -> RispResult<RispFunction> {
+    let clauses = clauses
+        .iter()
+        .map(|clause| {
+            Ok(FnClause {
+                spec: Some(Rc::new(ParamSpec::parse(&clause.params)?)),
+                ..clause.clone()
+            })
+        })
+        .collect::<RispResult<Vec<_>>>()?;
+    Ok(RispFunction::Function {
+        clauses: clauses.into(),
+        env: Some(env.captured()),
+    })
+}
+
+/// Calls f with already evaluated args and runs it to completion.
+fn apply(f: &RispFunction, args: &[RispExp], env: &mut RispEnv) -> RispResult<RispExp> {
+    run(call_function(f, args, env)?)
+}
+
+/// (if test then else), only the branch that is chosen is evaluated and it is in tail position.
+fn op_if(rest: &[RispExp], f: &RispFunction, env: &mut RispEnv) -> RispResult<Step> {
+    if rest.len() != 3 {
+        return Err(RispError::ArityMismatch(f.clone()));
+    }
+    let branch = if truthiness(&eval(&rest[0], env)?) {
+        &rest[1]
+    } else {
+        &rest[2]
+    };
+    Ok(Step::TailCall(branch.clone(), env.clone()))
+}
+
+/// (throw message payload?), raises an error that try can catch with the kind thrown.
+fn op_throw(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    let (message, payload) = match args {
+        [message] => (message, RispExp::Nil),
+        [message, payload] => (message, payload.clone()),
+        _ => return Err(RispError::ArityMismatch(f.clone())),
+    };
+    let message = match message {
+        RispExp::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    Err(RispError::Thrown(message, payload))
+}
+
+/// (try body... (catch kind e handler...)... (finally cleanup...)), evaluates body and if it errors
+/// runs the handlers of the first catch whose kind matches [RispError::kind], with the pattern e
+/// bound to the error as a (kind message payload) list. The kind can also be a list of kinds or _ for any.
+/// The finally forms always run last, and an error they raise replaces the result.
+fn op_try(env: &mut RispEnv, rest: &[RispExp]) -> RispResult<RispExp> {
+    let clause_start = rest
+        .iter()
+        .position(|form| try_clause(form).is_some())
+        .unwrap_or(rest.len());
+    let (body, clauses) = rest.split_at(clause_start);
+
+    let mut catches = vec![];
+    let mut finally = None;
+    for clause in clauses {
+        match (try_clause(clause), finally) {
+            (
+                Some((
+                    CATCH_SYM,
+                    [kinds, pattern @ (RispExp::Symbol(_) | RispExp::List(_)), handler @ ..],
+                )),
+                None,
+            ) => catches.push((kinds, pattern, handler)),
+            (Some((FINALLY_SYM, cleanup)), None) => finally = Some(cleanup),
+            _ => return Err(RispError::MalformedTryExpression),
+        }
+    }
+
+    let result = eval_body_to_completion(body, env).or_else(|e| {
+        match catches
+            .iter()
+            .find(|(kinds, _, _)| catches_kind(kinds, e.kind()))
+        {
+            Some((_, pattern, handler)) => {
+                let mut scope = RispEnv::with_outer(env);
+                bind_pattern(&scope, pattern, &error_value(e))?;
+                eval_body_to_completion(handler, &mut scope)
+            }
+            None => Err(e),
+        }
+    });
+    if let Some(cleanup) = finally {
+        eval_body_to_completion(cleanup, env)?;
+    }
+    result
+}
+
+/// The (catch ...) or (finally ...) clause name and its args if form is one.
+fn try_clause(form: &RispExp) -> Option<(&str, &[RispExp])> {
+    match form {
+        RispExp::List(forms) => match forms.split_first() {
+            Some((RispExp::Symbol(s), args)) if s == CATCH_SYM || s == FINALLY_SYM => {
+                Some((s.as_str(), args))
+            }
+            _ => None,
         },
+        _ => None,
+    }
+}
 
-        // Convert a literal into an executable.
-        RispExp::Lambda(f) => Ok(RispExp::Func(f.clone())),
+fn catches_kind(kinds: &RispExp, kind: &str) -> bool {
+    match kinds {
+        RispExp::Symbol(s) => s == WILDCARD_SYM || s == kind,
+        RispExp::List(kinds) => kinds.iter().any(|k| catches_kind(k, kind)),
+        _ => false,
+    }
+}
 
-        _ => Err(RispError::FirstFormMustBeFunction(first.clone())),
+/// A caught error as risp data, (kind message payload).
+fn error_value(e: RispError) -> RispExp {
+    let kind = RispExp::Symbol(e.kind().to_owned());
+    match e {
+        RispError::Thrown(message, payload) => {
+            RispExp::List(Rc::new([kind, RispExp::String(message), payload]))
+        }
+        e => RispExp::List(Rc::new([
+            kind,
+            RispExp::String(e.to_string()),
+            RispExp::Nil,
+        ])),
     }
 }
 
-fn op_if(rest: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
-    if rest.len() != 3 {
+/// Like [eval_body] but runs the last form too, for bodies that aren't in tail position.
+fn eval_body_to_completion(body: &[RispExp], env: &mut RispEnv) -> RispResult<RispExp> {
+    run(eval_body(body, env.clone())?)
+}
+
+/// (cond test expr ...), evaluates the expr of the first truthy test in tail position, or is nil if
+/// there is none. Since unbound symbols evaluate to themselves, else works as a last catch all test.
+fn op_cond(env: &mut RispEnv, rest: &[RispExp], f: &RispFunction) -> RispResult<Step> {
+    if !rest.len().is_multiple_of(2) {
+        return Err(RispError::MalformedClauses(f.to_string()));
+    }
+    for clause in rest.chunks(2) {
+        if truthiness(&eval(&clause[0], env)?) {
+            return Ok(Step::TailCall(clause[1].clone(), env.clone()));
+        }
+    }
+    Ok(Step::Done(RispExp::Nil))
+}
+
+/// (case expr constant result ... default?), compares the value of expr against each unevaluated
+/// constant, a list of constants matches any of them. An odd trailing form is the default.
+fn op_case(env: &mut RispEnv, rest: &[RispExp], f: &RispFunction) -> RispResult<Step> {
+    let (expr, clauses) = rest
+        .split_first()
+        .ok_or_else(|| RispError::ArityMismatch(f.clone()))?;
+    let value = eval(expr, env)?;
+    let mut clauses = clauses.chunks_exact(2);
+    for clause in &mut clauses {
+        let matches = match &clause[0] {
+            RispExp::List(constants) => constants.contains(&value),
+            constant => *constant == value,
+        };
+        if matches {
+            return Ok(Step::TailCall(clause[1].clone(), env.clone()));
+        }
+    }
+    match clauses.remainder() {
+        [default] => Ok(Step::TailCall(default.clone(), env.clone())),
+        _ => Err(RispError::NoMatchingClause(f.to_string(), value)),
+    }
+}
+
+/// (match expr pattern result ...), evaluates the result of the first pattern that matches the
+/// value of expr, with the pattern's symbols bound. Patterns are _ which matches anything, symbols
+/// which bind, literals and quoted forms which must be equal, and lists of patterns which may end
+/// in & and a pattern for the rest.
+fn op_match(env: &mut RispEnv, rest: &[RispExp], f: &RispFunction) -> RispResult<Step> {
+    let (expr, clauses) = rest
+        .split_first()
+        .ok_or_else(|| RispError::ArityMismatch(f.clone()))?;
+    if !clauses.len().is_multiple_of(2) {
+        return Err(RispError::MalformedClauses(f.to_string()));
+    }
+    let value = eval(expr, env)?;
+    for clause in clauses.chunks(2) {
+        let scope = RispEnv::with_outer(env);
+        if match_pattern(&scope, &clause[0], &value)? {
+            return Ok(Step::TailCall(clause[1].clone(), scope));
+        }
+    }
+    Err(RispError::NoMatchingClause(f.to_string(), value))
+}
+
+/// Like [bind_pattern] but a mismatch is false rather than an error, and it also takes the
+/// wildcard, literals and quoted forms described in [op_match].
+fn match_pattern(env: &RispEnv, pattern: &RispExp, value: &RispExp) -> RispResult<bool> {
+    match pattern {
+        RispExp::Symbol(s) if s == WILDCARD_SYM => Ok(true),
+        RispExp::Symbol(name) => env.def(name, value).map(|_| true),
+        RispExp::List(quoted)
+            if matches!(
+                quoted.first(),
+                Some(RispExp::Func(RispFunction::Builtin(
+                    RispBuiltinFunction::Quote
+                )))
+            ) =>
+        {
+            Ok(quoted.len() == 2 && quoted[1] == *value)
+        }
+        RispExp::List(patterns) | RispExp::Vector(patterns) => {
+            let values = match value {
+                RispExp::List(values) | RispExp::Vector(values) => values,
+                _ => return Ok(false),
+            };
+            let (patterns, rest) = split_rest_pattern(pattern, patterns)?;
+            if values.len() < patterns.len() || (rest.is_none() && values.len() > patterns.len()) {
+                return Ok(false);
+            }
+            for (pattern, value) in patterns.iter().zip(values.iter()) {
+                if !match_pattern(env, pattern, value)? {
+                    return Ok(false);
+                }
+            }
+            match rest {
+                Some(rest) => {
+                    match_pattern(env, rest, &RispExp::List(values[patterns.len()..].into()))
+                }
+                None => Ok(true),
+            }
+        }
+        RispExp::Map(patterns) => {
+            let map = match value {
+                RispExp::Map(map) => map,
+                _ => return Ok(false),
+            };
+            for (pattern, key) in patterns.iter() {
+                match map.get(key) {
+                    Some(value) if match_pattern(env, pattern, value)? => {}
+                    _ => return Ok(false),
+                }
+            }
+            Ok(true)
+        }
+        RispExp::Nil
+        | RispExp::Bool(_)
+        | RispExp::Keyword(_)
+        | RispExp::Integer(_)
+        | RispExp::BigInt(_)
+        | RispExp::Ratio(_)
+        | RispExp::Float(_)
+        | RispExp::Char(_)
+        | RispExp::String(_) => Ok(pattern == value),
+        _ => Err(malformed_pattern(pattern)),
+    }
+}
+
+/// (let (name expr ...) body...), every expr is evaluated in the enclosing env and then bound in a
+/// new scope for the body.
+fn op_let(env: &mut RispEnv, rest: &[RispExp], f: &RispFunction) -> RispResult<Step> {
+    let (bindings, body) = split_binding_form(rest, f)?;
+    let values = bindings
+        .iter()
+        .map(|(_, expr)| eval(expr, env))
+        .collect::<RispResult<Vec<RispExp>>>()?;
+
+    let scope = RispEnv::with_outer(env);
+    for ((pattern, _), value) in bindings.iter().zip(values) {
+        bind_pattern(&scope, pattern, &value)?;
+    }
+    eval_body(body, scope)
+}
+
+/// (let* (name expr ...) body...), like let but each expr can see the names bound before it.
+fn op_let_star(env: &mut RispEnv, rest: &[RispExp], f: &RispFunction) -> RispResult<Step> {
+    let (bindings, body) = split_binding_form(rest, f)?;
+    let mut scope = RispEnv::with_outer(env);
+    for (pattern, expr) in bindings {
+        let value = eval(expr, &mut scope)?;
+        bind_pattern(&scope, pattern, &value)?;
+    }
+    eval_body(body, scope)
+}
+
+/// (letrec (name expr ...) body...), every expr is evaluated in the new scope with all the names
+/// already in it, so local functions can call each other.
+fn op_letrec(env: &mut RispEnv, rest: &[RispExp], f: &RispFunction) -> RispResult<Step> {
+    let (bindings, body) = split_binding_form(rest, f)?;
+    let mut scope = RispEnv::with_outer(env);
+    for (pattern, _) in &bindings {
+        if let RispExp::Symbol(name) = pattern {
+            scope.def(name, &RispExp::Nil)?;
+        }
+    }
+    for (pattern, expr) in bindings {
+        let value = eval(expr, &mut scope)?;
+        bind_pattern(&scope, pattern, &value)?;
+    }
+    eval_body(body, scope)
+}
+
+/// (loop (pattern expr ...) body...), binds like let* and runs the body, a (recur args...) in the
+/// tail of the body rebinds the patterns to args and runs the body again.
+fn op_loop(env: &mut RispEnv, rest: &[RispExp], f: &RispFunction) -> RispResult<Step> {
+    let (bindings, body) = split_binding_form(rest, f)?;
+    let mut scope = RispEnv::with_outer(env);
+    for (pattern, expr) in &bindings {
+        let value = eval(expr, &mut scope)?;
+        bind_pattern(&scope, pattern, &value)?;
+    }
+    let target = RecurTarget::Loop {
+        patterns: bindings
+            .iter()
+            .map(|(pattern, _)| (*pattern).clone())
+            .collect(),
+        body: body.into(),
+        env: env.clone(),
+    };
+    Ok(Step::Enter(target, Box::new(eval_body(body, scope)?)))
+}
+
+/// (recur args...), evaluates args and hands them to [run] to jump back to the enclosing loop or fn.
+fn op_recur(env: &mut RispEnv, rest: &[RispExp]) -> RispResult<Step> {
+    let args = rest
+        .iter()
+        .map(|x| eval(x, env))
+        .collect::<RispResult<Vec<RispExp>>>()?;
+    Ok(Step::Recur(args))
+}
+
+/// The (pattern, expr) pairs of a binding form, in the order they were written.
+type Bindings<'a> = Vec<(&'a RispExp, &'a RispExp)>;
+
+/// Splits (form (pattern expr ...) body...) into its (pattern, expr) pairs and its body.
+fn split_binding_form<'a>(
+    rest: &'a [RispExp],
+    f: &RispFunction,
+) -> RispResult<(Bindings<'a>, &'a [RispExp])> {
+    let malformed = || RispError::MalformedBindingForm(f.to_string());
+    let (bindings, body) = match rest.split_first() {
+        Some((RispExp::List(bindings), body)) if bindings.len() % 2 == 0 => (bindings, body),
+        Some(_) => return Err(malformed()),
+        None => return Err(RispError::ArityMismatch(f.clone())),
+    };
+    let pairs = bindings
+        .chunks(2)
+        .map(|pair| match &pair[0] {
+            pattern if is_pattern(pattern) => Ok((pattern, &pair[1])),
+            _ => Err(malformed()),
+        })
+        .collect::<RispResult<Vec<_>>>()?;
+    Ok((pairs, body))
+}
+
+/// Evaluates a body of forms in order in env, the last one is in tail position. An empty body is nil.
+fn eval_body(body: &[RispExp], mut env: RispEnv) -> RispResult<Step> {
+    match body.split_last() {
+        Some((last, init)) => {
+            for form in init {
+                eval(form, &mut env)?;
+            }
+            Ok(Step::TailCall(last.clone(), env))
+        }
+        None => Ok(Step::Done(RispExp::Nil)),
+    }
+}
+
+/// (quote x), returns x as data without evaluating it.
+fn op_quote(rest: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    match rest {
+        [quoted] => Ok(quoted.clone()),
+        _ => Err(RispError::ArityMismatch(f.clone())),
+    }
+}
+
+/// (quasiquote x), returns x as data except for the (unquote y) forms inside it, which are
+/// evaluated, and (unquote-splicing ys) forms, whose list is evaluated and spliced in place.
+fn op_quasiquote(rest: &[RispExp], f: &RispFunction, env: &mut RispEnv) -> RispResult<RispExp> {
+    match rest {
+        [template] => quasiquote(template, env),
+        _ => Err(RispError::ArityMismatch(f.clone())),
+    }
+}
+
+fn quasiquote(template: &RispExp, env: &mut RispEnv) -> RispResult<RispExp> {
+    let forms = match template {
+        RispExp::List(forms) => forms,
+        RispExp::Vector(forms) => return Ok(RispExp::Vector(quasiquote_items(forms, env)?.into())),
+        RispExp::Map(map) => {
+            return Ok(RispExp::Map(Box::new(
+                map.iter()
+                    .map(|(k, v)| Ok((quasiquote(k, env)?, quasiquote(v, env)?)))
+                    .collect::<RispResult<_>>()?,
+            )))
+        }
+        RispExp::Set(set) => {
+            return Ok(RispExp::Set(Box::new(
+                set.iter()
+                    .map(|item| quasiquote(item, env))
+                    .collect::<RispResult<_>>()?,
+            )))
+        }
+        _ => return Ok(template.clone()),
+    };
+    if let Some(unquoted) = unquote_target(forms, RispBuiltinFunction::Unquote) {
+        return eval(unquoted, env);
+    }
+    Ok(RispExp::List(quasiquote_items(forms, env)?.into()))
+}
+
+/// Quasiquotes each of forms, splicing in the items of any (unquote-splicing x).
+fn quasiquote_items(forms: &[RispExp], env: &mut RispEnv) -> RispResult<Vec<RispExp>> {
+    let mut result = Vec::with_capacity(forms.len());
+    for form in forms {
+        match form {
+            RispExp::List(inner) => {
+                match unquote_target(inner, RispBuiltinFunction::UnquoteSplicing) {
+                    Some(spliced) => match eval(spliced, env)? {
+                        RispExp::List(items) | RispExp::Vector(items) => {
+                            result.extend(items.iter().cloned())
+                        }
+                        RispExp::Nil => {}
+                        _ => return Err(RispError::TypeError(UNQUOTE_SPLICING_EXPECTS_LIST)),
+                    },
+                    None => result.push(quasiquote(form, env)?),
+                }
+            }
+            _ => result.push(quasiquote(form, env)?),
+        }
+    }
+    Ok(result)
+}
+
+/// If forms is (unquote x) or (unquote-splicing x), depending on which is asked for, returns x.
+fn unquote_target(forms: &[RispExp], unquote: RispBuiltinFunction) -> Option<&RispExp> {
+    match forms {
+        [RispExp::Func(RispFunction::Builtin(b)), target] if *b == unquote => Some(target),
+        _ => None,
+    }
+}
+
+/// (defmacro name (params) body), defines a macro. When (name args) is evaluated the body is run with
+/// the params bound to the unevaluated args, and the code it returns is evaluated in its place. The
+/// params are written like those of fn, as a list or a vector, see [ParamSpec].
+fn op_defmacro(env: &mut RispEnv, rest: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    if rest.len() < 3 {
         return Err(RispError::ArityMismatch(f.clone()));
     }
-    if let RispExp::Bool(b) = rest.get(0).unwrap() {
-        let true_branch = rest.get(1).unwrap();
-        let else_branch = rest.get(2).unwrap();
-        return Ok(if *b {
-            true_branch.clone()
-        } else {
-            else_branch.clone()
-        });
+    match (&rest[0], &rest[1]) {
+        (RispExp::Symbol(s), _) if RispFunction::is_builtin(s) => {
+            Err(RispError::InvalidName(s.clone()))
+        }
+        (RispExp::Symbol(name), params @ (RispExp::List(_) | RispExp::Vector(_))) => {
+            let clause = FnClause::new(params.clone(), rest[2..].to_vec());
+            let transformer = close_over(&[clause], env)?;
+            env.def(name, &RispExp::Macro(transformer))
+        }
+        _ => Err(RispError::MalformedDefmacroExpression),
     }
-    return Err(RispError::MalformedDefExpression);
 }
 
+/// If forms is a call to a macro, expands it once. Returns None when it isn't a macro call.
+fn macroexpand_1(forms: &[RispExp], env: &mut RispEnv) -> RispResult<Option<RispExp>> {
+    if let Some((RispExp::Symbol(name), args)) = forms.split_first() {
+        if let Ok(RispExp::Macro(transformer)) = env.get(name) {
+            return apply(&transformer, args, env).map(Some);
+        }
+    }
+    Ok(None)
+}
+
+/// (macroexpand-1 form), expands form once if it is a macro call, otherwise returns it as is.
+fn op_macroexpand_1(env: &mut RispEnv, rest: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    match rest {
+        [form @ RispExp::List(forms)] => {
+            Ok(macroexpand_1(forms, env)?.unwrap_or_else(|| form.clone()))
+        }
+        [form] => Ok(form.clone()),
+        _ => Err(RispError::ArityMismatch(f.clone())),
+    }
+}
+
+/// (macroexpand form), expands form until it is no longer a macro call.
+fn op_macroexpand(env: &mut RispEnv, rest: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    let mut form = match rest {
+        [form] => form.clone(),
+        _ => return Err(RispError::ArityMismatch(f.clone())),
+    };
+    while let RispExp::List(forms) = &form {
+        match macroexpand_1(forms, env)? {
+            Some(expansion) => form = expansion,
+            None => break,
+        }
+    }
+    Ok(form)
+}
+
+/// (def name expr), binds name to the value of expr in env. The name isn't evaluated, so a name that
+/// is already bound, like a library function, can be defined again. Builtin names are read as the
+/// builtin itself rather than a symbol, and can't be defined.
 fn op_def(env: &mut RispEnv, rest: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
     if rest.len() != 2 {
         return Err(RispError::ArityMismatch(f.clone()));
     }
-    match rest.get(0).unwrap() {
-        RispExp::Symbol(s) if RispFunction::is_builtin(s) => Err(RispError::InvalidName(s.clone())),
+    match &rest[0] {
+        RispExp::Func(builtin @ RispFunction::Builtin(_)) => {
+            Err(RispError::InvalidName(builtin.to_string()))
+        }
         RispExp::Symbol(name) => {
-            let expr = rest.get(1).unwrap();
-            env.def(name, expr)
+            let value = eval(&rest[1], env)?;
+            env.def(name, &value)
         }
         _ => Err(RispError::MalformedDefExpression),
     }
 }
 
-fn env_for_lambda<'a, 'b>(
-    f: &'a RispFunction,
-    params: &'a RispExp,
-    bindings: &'a [RispExp],
-    parent: &'b mut RispEnv,
-) -> RispResult<RispEnv<'b>> {
-    let p = expr_to_list(params)?;
-    if p.len() != bindings.len() {
-        return Err(RispError::ArityMismatch(f.clone()));
+/// (set! name expr), rebinds the nearest existing binding of name to the value of expr.
+fn op_set(env: &mut RispEnv, rest: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    match rest {
+        [RispExp::Symbol(name), expr] => {
+            let value = eval(expr, env)?;
+            env.set(name, &value)
+        }
+        [_, _] => Err(RispError::MalformedSetExpression),
+        _ => Err(RispError::ArityMismatch(f.clone())),
+    }
+}
+
+fn op_atom(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    match args {
+        [value] => Ok(RispExp::Atom(RispAtom::new(value.clone()))),
+        _ => Err(RispError::ArityMismatch(f.clone())),
+    }
+}
+
+fn op_deref(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    match args {
+        [atom] => Ok(expr_to_atom(atom)?.value()),
+        _ => Err(RispError::ArityMismatch(f.clone())),
+    }
+}
+
+/// (reset! atom value), sets the atom to value and returns it.
+fn op_reset(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    match args {
+        [atom, value] => Ok(expr_to_atom(atom)?.reset(value.clone())),
+        _ => Err(RispError::ArityMismatch(f.clone())),
+    }
+}
+
+/// (swap! atom f args...), sets the atom to (f current-value args...) and returns it.
+fn op_swap(env: &mut RispEnv, args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    match args {
+        [atom, RispExp::Func(swap_fn), swap_args @ ..] => {
+            let atom = expr_to_atom(atom)?;
+            let mut call_args = vec![atom.value()];
+            call_args.extend_from_slice(swap_args);
+            let value = apply(swap_fn, &call_args, env)?;
+            Ok(atom.reset(value))
+        }
+        [_, not_fn, ..] => Err(RispError::FirstFormMustBeFunction(not_fn.clone())),
+        _ => Err(RispError::ArityMismatch(f.clone())),
     }
+}
 
+/// Binds bindings to the params in spec, which must accept that many args.
+fn env_for_lambda(spec: &ParamSpec, bindings: &[RispExp], parent: &RispEnv) -> RispResult<RispEnv> {
     let mut res = RispEnv::with_outer(parent);
-    for (param, binding) in p.iter().zip(bindings) {
-        res.def(expr_to_symbol(param)?, binding)?;
+    let mut args = bindings.iter();
+    for (pattern, arg) in spec.required.iter().zip(&mut args) {
+        bind_pattern(&res, pattern, arg)?;
+    }
+    // Defaults are evaluated in the call's scope so they can refer to the params before them.
+    for (pattern, default) in &spec.optional {
+        let value = match (args.next(), default) {
+            (Some(arg), _) => arg.clone(),
+            (None, Some(default)) => eval(default, &mut res)?,
+            (None, None) => RispExp::Nil,
+        };
+        bind_pattern(&res, pattern, &value)?;
+    }
+    if let Some(pattern) = &spec.rest {
+        bind_pattern(&res, pattern, &RispExp::List(args.as_slice().into()))?;
+    }
+    if !spec.keys.is_empty() {
+        // The remaining args are :keyword value pairs, spec accepting them means there is an even
+        // number of them.
+        let mut values = vec![None; spec.keys.len()];
+        for pair in args.as_slice().chunks(2) {
+            let key = match &pair[0] {
+                RispExp::Keyword(k) => spec.keys.iter().position(|(name, _)| name == k),
+                _ => None,
+            }
+            .ok_or_else(|| {
+                RispError::UnexpectedExpr(pair[0].clone(), UNKNOWN_KEYWORD_ARG.to_owned())
+            })?;
+            values[key] = Some(pair[1].clone());
+        }
+        for ((name, default), value) in spec.keys.iter().zip(values) {
+            let value = match (value, default) {
+                (Some(value), _) => value,
+                (None, Some(default)) => eval(default, &mut res)?,
+                (None, None) => RispExp::Nil,
+            };
+            res.def(name, &value)?;
+        }
     }
     Ok(res)
 }
 
-// TODO on math functions handle overflow/cases
+/// Binds value to pattern in env. A pattern is a symbol, a list or vector of patterns that
+/// destructures a list or vector of the same length, eg (a [b c] & more), or a map of patterns to
+/// keys that destructures the values of those keys in a map, eg {a :a b :b}, binding nil for the
+/// missing ones. A mismatch reports the innermost pattern that failed.
+fn bind_pattern(env: &RispEnv, pattern: &RispExp, value: &RispExp) -> RispResult<()> {
+    let mismatch = || RispError::PatternMismatch(pattern.clone(), value.clone());
+    match pattern {
+        RispExp::Symbol(name) => env.def(name, value).map(|_| ()),
+        RispExp::List(patterns) | RispExp::Vector(patterns) => {
+            let values = match value {
+                RispExp::List(values) | RispExp::Vector(values) => values,
+                _ => return Err(mismatch()),
+            };
+            let (patterns, rest) = split_rest_pattern(pattern, patterns)?;
+            if values.len() < patterns.len() || (rest.is_none() && values.len() > patterns.len()) {
+                return Err(mismatch());
+            }
+            for (pattern, value) in patterns.iter().zip(values.iter()) {
+                bind_pattern(env, pattern, value)?;
+            }
+            if let Some(rest) = rest {
+                bind_pattern(env, rest, &RispExp::List(values[patterns.len()..].into()))?;
+            }
+            Ok(())
+        }
+        RispExp::Map(patterns) => {
+            let map = match value {
+                RispExp::Map(map) => map,
+                _ => return Err(mismatch()),
+            };
+            for (pattern, key) in patterns.iter() {
+                bind_pattern(env, pattern, map.get(key).unwrap_or(&RispExp::Nil))?;
+            }
+            Ok(())
+        }
+        _ => Err(malformed_pattern(pattern)),
+    }
+}
+
+fn is_pattern(exp: &RispExp) -> bool {
+    matches!(
+        exp,
+        RispExp::Symbol(_) | RispExp::List(_) | RispExp::Vector(_) | RispExp::Map(_)
+    )
+}
+
+/// Splits the patterns of a list or vector pattern into the ones matched one to one and the one
+/// after &, which takes the rest.
+fn split_rest_pattern<'a>(
+    pattern: &RispExp,
+    patterns: &'a [RispExp],
+) -> RispResult<(&'a [RispExp], Option<&'a RispExp>)> {
+    match patterns.iter().position(is_rest_marker) {
+        Some(i) => match &patterns[i + 1..] {
+            [rest] => Ok((&patterns[..i], Some(rest))),
+            _ => Err(malformed_pattern(pattern)),
+        },
+        None => Ok((patterns, None)),
+    }
+}
+
+fn is_rest_marker(exp: &RispExp) -> bool {
+    matches!(exp, RispExp::Symbol(s) if s == REST_PARAM_SYM)
+}
+
+fn malformed_pattern(pattern: &RispExp) -> RispError {
+    RispError::UnexpectedExpr(pattern.clone(), MALFORMED_PATTERN.to_owned())
+}
+
+/// The params of a fn, eg (a b &opt c (d 1) & more) has required a and b, optional c (defaulting
+/// to nil) and d (defaulting to 1), and collects any other args into more. Each of them is a
+/// pattern, see [bind_pattern]. Instead of optional and rest params a fn can take keyword params,
+/// eg (a &key b (c 1)) is called like (f 1 :c 2) and binds b and c by name.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct ParamSpec {
+    required: Vec<RispExp>,
+    optional: Vec<(RispExp, Option<RispExp>)>,
+    rest: Option<RispExp>,
+    keys: Vec<(String, Option<RispExp>)>,
+}
+
+impl ParamSpec {
+    fn parse(params: &RispExp) -> RispResult<ParamSpec> {
+        let malformed =
+            |param: &RispExp| RispError::UnexpectedExpr(param.clone(), MALFORMED_PARAMS.to_owned());
+        let mut spec = ParamSpec::default();
+        let mut in_optional = false;
+        let mut in_keys = false;
+        let params = match params {
+            RispExp::Vector(params) => &params[..],
+            _ => expr_to_list(params)?,
+        };
+        let mut params = params.iter();
+        while let Some(param) = params.next() {
+            match param {
+                RispExp::Symbol(s) if s == KEY_PARAM_SYM => {
+                    if in_optional || in_keys {
+                        return Err(malformed(param));
+                    }
+                    in_keys = true;
+                }
+                RispExp::Symbol(s)
+                    if in_keys && (s == REST_PARAM_SYM || s == OPTIONAL_PARAM_SYM) =>
+                {
+                    return Err(malformed(param))
+                }
+                RispExp::Symbol(s) if in_keys => spec.keys.push((s.clone(), None)),
+                RispExp::List(pair) if in_keys => match &pair[..] {
+                    [RispExp::Symbol(s), default] => {
+                        spec.keys.push((s.clone(), Some(default.clone())))
+                    }
+                    _ => return Err(malformed(param)),
+                },
+                _ if in_keys => return Err(malformed(param)),
+                RispExp::Symbol(s) if s == REST_PARAM_SYM => match (params.next(), params.next()) {
+                    (Some(pattern), None) if is_pattern(pattern) => {
+                        spec.rest = Some(pattern.clone())
+                    }
+                    _ => return Err(malformed(param)),
+                },
+                RispExp::Symbol(s) if s == OPTIONAL_PARAM_SYM => {
+                    if in_optional {
+                        return Err(malformed(param));
+                    }
+                    in_optional = true;
+                }
+                RispExp::List(pair) if in_optional => match &pair[..] {
+                    [pattern, default] if is_pattern(pattern) => {
+                        spec.optional.push((pattern.clone(), Some(default.clone())))
+                    }
+                    _ => return Err(malformed(param)),
+                },
+                _ if in_optional && is_pattern(param) => spec.optional.push((param.clone(), None)),
+                _ if is_pattern(param) => spec.required.push(param.clone()),
+                _ => return Err(malformed(param)),
+            }
+        }
+        Ok(spec)
+    }
+
+    fn accepts(&self, arg_count: usize) -> bool {
+        if !self.keys.is_empty() {
+            return arg_count >= self.required.len()
+                && (arg_count - self.required.len()).is_multiple_of(2);
+        }
+        let max_args = self.required.len() + self.optional.len();
+        arg_count >= self.required.len() && (self.rest.is_some() || arg_count <= max_args)
+    }
+
+    /// The number of args accepted, eg "2", "1 to 3", "2 or more" or "1 and keyword".
+    fn arity(&self) -> String {
+        let min_args = self.required.len();
+        if !self.keys.is_empty() {
+            return format!("{} and keyword", min_args);
+        }
+        match (self.optional.len(), &self.rest) {
+            (_, Some(_)) => format!("{} or more", min_args),
+            (0, None) => min_args.to_string(),
+            (optional, None) => format!("{} to {}", min_args, min_args + optional),
+        }
+    }
+}
+
+/// Describes the arities f accepts for [RispError::ArityMismatch], builtins check their own args
+/// so there is nothing to add for them.
+pub(crate) fn accepted_arities(f: &RispFunction) -> String {
+    match f {
+        RispFunction::Function { clauses, .. } => {
+            let arities = clauses
+                .iter()
+                .filter_map(|clause| clause.spec.as_ref().map(|spec| spec.arity()))
+                .collect::<Vec<String>>();
+            format!(", it accepts {} args", arities.join(", "))
+        }
+        RispFunction::Builtin(_) => "".to_owned(),
+    }
+}
+
 pub fn plus(args: &[RispExp]) -> RispResult<RispExp> {
-    number_list_apply!(args, Iterator::sum)
+    number_list_apply!(args, 0, std::ops::Add::add, i64::checked_add)
 }
 
 pub fn minus(args: &[RispExp]) -> RispResult<RispExp> {
-    return number_list_subtractive_apply!(
+    number_list_subtractive_apply!(
         args,
         RispExp::Integer(0),
         std::ops::Sub::sub,
-        Iterator::sum
-    );
+        i64::checked_sub
+    )
 }
 
 pub fn multiply(args: &[RispExp]) -> RispResult<RispExp> {
-    number_list_apply!(args, Iterator::product)
+    number_list_apply!(args, 1, std::ops::Mul::mul, i64::checked_mul)
 }
 
 pub fn divide(args: &[RispExp]) -> RispResult<RispExp> {
-    if args.len() < 1 {
+    if args.is_empty() {
         return Err(RispError::ArityMismatch(RispFunction::Builtin(
             RispBuiltinFunction::Divide,
         )));
     }
 
-    number_list_subtractive_apply!(
-        args,
-        RispExp::Integer(0),
-        std::ops::Div::div,
-        Iterator::product
-    )
+    check_for_illegal_arithmetic_input(args)?;
+
+    // Integer division is exact, it makes a ratio unless the result is whole.
+    let (first, rest) = args.split_first().unwrap();
+    if args.iter().any(|arg| matches!(arg, RispExp::Float(_))) {
+        return Ok(RispExp::Float(
+            rest.iter()
+                .map(expr_to_float)
+                .fold(expr_to_float(first), Div::div),
+        ));
+    }
+    if rest.iter().any(|arg| expr_to_ratio(arg).is_zero()) {
+        return Err(RispError::DivisionByZero);
+    }
+    Ok(rest
+        .iter()
+        .map(expr_to_ratio)
+        .fold(expr_to_ratio(first), Div::div)
+        .into())
 }
 
-/// Boolean and all args.  The only false values are nil and false.
-pub fn boolean_and(args: &[RispExp]) -> RispResult<RispExp> {
-    Ok(RispExp::Bool(
-        args.iter()
-            .map(truthiness)
-            .reduce(|x, y| x && y)
-            .unwrap_or(true),
-    ))
+/// Boolean and all args, stopping at the first falsey one.  The only false values are nil and false.
+pub fn boolean_and(args: &[RispExp], env: &mut RispEnv) -> RispResult<RispExp> {
+    for arg in args {
+        if !truthiness(&eval(arg, env)?) {
+            return Ok(RispExp::Bool(false));
+        }
+    }
+    Ok(RispExp::Bool(true))
 }
 
-pub fn boolean_or(args: &[RispExp]) -> RispResult<RispExp> {
-    Ok(RispExp::Bool(
-        args.iter()
-            .map(truthiness)
-            .reduce(|x, y| x || y)
-            .unwrap_or(false),
-    ))
+/// Boolean or all args, stopping at the first truthy one.
+pub fn boolean_or(args: &[RispExp], env: &mut RispEnv) -> RispResult<RispExp> {
+    for arg in args {
+        if truthiness(&eval(arg, env)?) {
+            return Ok(RispExp::Bool(true));
+        }
+    }
+    Ok(RispExp::Bool(false))
 }
 
 pub fn boolean_xor(args: &[RispExp]) -> RispResult<RispExp> {
@@ -178,89 +1229,68 @@ pub fn boolean_not(args: &[RispExp]) -> RispResult<RispExp> {
 }
 
 pub fn op_lt(rest: &[RispExp]) -> RispResult<RispExp> {
-    check_arity_is_two_or_less(
-        rest,
-        RispError::ArityMismatch(RispFunction::Builtin(RispBuiltinFunction::LT)),
-    )?;
-    if rest.len() == 1 {
-        return Ok(RispExp::Bool(true));
-    }
-    match (rest.get(0).unwrap(), rest.get(1).unwrap()) {
-        (a, b) => Ok(RispExp::Bool(a < b)),
-    }
+    compare_chain(rest, RispBuiltinFunction::LT, Ordering::is_lt)
 }
 
 pub fn op_lte(rest: &[RispExp]) -> RispResult<RispExp> {
-    check_arity_is_two_or_less(
-        rest,
-        RispError::ArityMismatch(RispFunction::Builtin(RispBuiltinFunction::LTE)),
-    )?;
-    if rest.len() == 1 {
-        return Ok(RispExp::Bool(true));
-    }
-    match (rest.get(0).unwrap(), rest.get(1).unwrap()) {
-        (a, b) => Ok(RispExp::Bool(a <= b)),
-    }
+    compare_chain(rest, RispBuiltinFunction::LTE, Ordering::is_le)
 }
 
 pub fn op_gt(rest: &[RispExp]) -> RispResult<RispExp> {
-    check_arity_is_two_or_less(
-        rest,
-        RispError::ArityMismatch(RispFunction::Builtin(RispBuiltinFunction::GT)),
-    )?;
-    if rest.len() == 1 {
-        return Ok(RispExp::Bool(true));
-    }
-    match (rest.get(0).unwrap(), rest.get(1).unwrap()) {
-        (a, b) => Ok(RispExp::Bool(a > b)),
-    }
+    compare_chain(rest, RispBuiltinFunction::GT, Ordering::is_gt)
 }
 
 pub fn op_gte(rest: &[RispExp]) -> RispResult<RispExp> {
-    check_arity_is_two_or_less(
-        rest,
-        RispError::ArityMismatch(RispFunction::Builtin(RispBuiltinFunction::GTE)),
-    )?;
-    if rest.len() == 1 {
-        return Ok(RispExp::Bool(true));
-    }
-    match (rest.get(0).unwrap(), rest.get(1).unwrap()) {
-        (a, b) => Ok(RispExp::Bool(a >= b)),
-    }
+    compare_chain(rest, RispBuiltinFunction::GTE, Ordering::is_ge)
 }
 
+/// (= a b c), true when every adjacent pair is equal. Values of any type can be compared.
 pub fn op_eq(rest: &[RispExp]) -> RispResult<RispExp> {
-    check_arity_is_two_or_less(
-        rest,
-        RispError::ArityMismatch(RispFunction::Builtin(RispBuiltinFunction::EQ)),
-    )?;
-    if rest.len() == 1 {
-        return Ok(RispExp::Bool(true));
-    }
-    match (rest.get(0).unwrap(), rest.get(1).unwrap()) {
-        (a, b) => Ok(RispExp::Bool(a == b)),
+    if rest.is_empty() {
+        return Err(RispError::ArityMismatch(RispFunction::Builtin(
+            RispBuiltinFunction::EQ,
+        )));
     }
+    Ok(RispExp::Bool(
+        rest.windows(2).all(|pair| pair[0] == pair[1]),
+    ))
 }
 
-fn check_arity_is_two_or_less(rest: &[RispExp], error: RispError) -> RispResult<()> {
-    if rest.len() > 2 {
-        return Err(error);
+/// True when every adjacent pair of args is ordered the way holds says, like (< 1 2 3). Every pair
+/// is checked so a value that can't be ordered is an error even after a false pair.
+fn compare_chain(
+    rest: &[RispExp],
+    op: RispBuiltinFunction,
+    holds: fn(Ordering) -> bool,
+) -> RispResult<RispExp> {
+    if rest.is_empty() {
+        return Err(RispError::ArityMismatch(RispFunction::Builtin(op)));
+    }
+    let mut result = true;
+    for pair in rest.windows(2) {
+        match pair[0].partial_cmp(&pair[1]) {
+            Some(ordering) => result &= holds(ordering),
+            // NaN is a number but isn't ordered against anything.
+            None if is_number(&pair[0]) && is_number(&pair[1]) => result = false,
+            None => return Err(RispError::TypeError(INCOMPARABLE_TYPES)),
+        }
     }
-    Ok(())
+    Ok(RispExp::Bool(result))
 }
 
 fn truthiness(b: &RispExp) -> bool {
-    match b {
-        RispExp::Nil => false,
-        RispExp::Bool(false) => false,
-        _ => true,
-    }
+    !matches!(b, RispExp::Nil | RispExp::Bool(false))
+}
+
+fn is_number(exp: &RispExp) -> bool {
+    matches!(
+        exp,
+        RispExp::Integer(_) | RispExp::BigInt(_) | RispExp::Ratio(_) | RispExp::Float(_)
+    )
 }
+
 fn check_for_illegal_arithmetic_input(args: &[RispExp]) -> RispResult<()> {
-    if args
-        .iter()
-        .any(|arg| !(matches!(arg, RispExp::Integer(_) | RispExp::Float(_))))
-    {
+    if !args.iter().all(is_number) {
         return Err(RispError::TypeError(ILLEGAL_TYPE_FOR_ARITHMETIC_OP));
     }
 
@@ -268,91 +1298,103 @@ fn check_for_illegal_arithmetic_input(args: &[RispExp]) -> RispResult<()> {
 }
 
 fn expr_to_float(arg: &RispExp) -> f64 {
+    arg.to_float().unwrap()
+}
+
+fn expr_to_ratio(arg: &RispExp) -> BigRational {
+    arg.to_ratio().unwrap()
+}
+
+fn expr_to_bigint(arg: &RispExp) -> BigInt {
     match arg {
-        RispExp::Integer(i) => f64::from(*i),
-        RispExp::Float(f) => *f,
+        RispExp::Integer(i) => BigInt::from(*i),
+        RispExp::BigInt(i) => i.clone(),
         _ => panic!(),
     }
 }
 
-fn expr_to_int(arg: &RispExp) -> i32 {
+fn expr_to_int(arg: &RispExp) -> RispResult<i64> {
     match arg {
-        RispExp::Integer(i) => *i,
-        _ => panic!(),
+        RispExp::Integer(i) => Ok(*i),
+        _ => Err(RispError::TypeError(EXPECTED_INTEGER)),
     }
 }
 
-fn expr_to_symbol(arg: &RispExp) -> RispResult<&str> {
+fn expr_to_str(arg: &RispExp) -> RispResult<&str> {
     match arg {
-        RispExp::Symbol(s) => Ok(s.as_str()),
-        _ => {
-            return Err(RispError::GenericError(format!(
-                "{0:?} should be a symbol",
-                arg
-            )))
-        }
+        RispExp::String(s) => Ok(s),
+        _ => Err(RispError::TypeError(EXPECTED_STRING)),
+    }
+}
+
+fn expr_to_atom(arg: &RispExp) -> RispResult<&RispAtom> {
+    match arg {
+        RispExp::Atom(a) => Ok(a),
+        _ => Err(RispError::TypeError(EXPECTED_ATOM)),
     }
 }
 
 fn expr_to_list(arg: &RispExp) -> RispResult<&[RispExp]> {
     match arg {
-        RispExp::List(p) => Ok(p.as_slice()),
-        _ => {
-            return Err(RispError::GenericError(format!(
-                "{0:?} should be a list",
-                arg
-            )))
-        }
+        RispExp::List(p) => Ok(p),
+        _ => Err(RispError::GenericError(format!(
+            "{0:?} should be a list",
+            arg
+        ))),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::error::{RispError, ILLEGAL_TYPE_FOR_ARITHMETIC_OP};
+    use num_bigint::BigInt;
+    use num_rational::BigRational;
     use pretty_assertions::assert_eq;
 
+    use std::rc::Rc;
+
     use crate::environment::RispEnv;
     use crate::eval::eval;
-    use crate::parser::{RispBuiltinFunction, RispExp, RispFunction};
+    use crate::parser::{FnClause, RispBuiltinFunction, RispExp, RispFunction};
 
     #[test]
     fn plus_2_or_more() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
             RispExp::Integer(37),
             RispExp::Integer(42),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(37 + 42));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
             RispExp::Integer(37),
             RispExp::Integer(42),
             RispExp::Integer(42),
             RispExp::Integer(42),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(37 + 3 * 42));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
             RispExp::Integer(37),
             RispExp::Integer(-42),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(37 - 42));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
             RispExp::Integer(-37),
             RispExp::Integer(42),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(-37 + 42));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
             RispExp::Float(-37f64),
             RispExp::Integer(42),
-        ]);
+        ]));
         assert_eq!(
             eval(&exp, &mut env).unwrap(),
             RispExp::Float(-37f64 + 42f64)
@@ -362,30 +1404,30 @@ mod tests {
     #[test]
     fn plus1() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
             RispExp::Integer(37),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(37));
     }
 
     #[test]
     fn plus0() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![RispExp::Func(RispFunction::Builtin(
+        let exp = RispExp::List(Rc::new([RispExp::Func(RispFunction::Builtin(
             RispBuiltinFunction::Plus,
-        ))]);
+        ))]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(0));
     }
 
     #[test]
     fn plus_non_number() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
             RispExp::String("Locutus".to_owned()),
             RispExp::Integer(42),
-        ]);
+        ]));
         assert_eq!(
             eval(&exp, &mut env).unwrap_err(),
             RispError::TypeError(ILLEGAL_TYPE_FOR_ARITHMETIC_OP)
@@ -395,41 +1437,41 @@ mod tests {
     #[test]
     fn minus_2_or_more() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Minus)),
             RispExp::Integer(37),
             RispExp::Integer(42),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(37 - 42));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Minus)),
             RispExp::Integer(37),
             RispExp::Integer(42),
             RispExp::Integer(42),
             RispExp::Integer(42),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(37 - 3 * 42));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Minus)),
             RispExp::Integer(37),
             RispExp::Integer(-42),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(37 - (-42)));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Minus)),
             RispExp::Integer(-37),
             RispExp::Integer(42),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(-37 - 42));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Minus)),
             RispExp::Float(-37f64),
             RispExp::Integer(42),
-        ]);
+        ]));
         assert_eq!(
             eval(&exp, &mut env).unwrap(),
             RispExp::Float(-37f64 - 42f64)
@@ -439,30 +1481,30 @@ mod tests {
     #[test]
     fn minus1() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Minus)),
             RispExp::Integer(37),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(37));
     }
 
     #[test]
     fn minus0() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![RispExp::Func(RispFunction::Builtin(
+        let exp = RispExp::List(Rc::new([RispExp::Func(RispFunction::Builtin(
             RispBuiltinFunction::Minus,
-        ))]);
+        ))]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(0));
     }
 
     #[test]
     fn minus_non_number() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Minus)),
             RispExp::String("Locutus".to_owned()),
             RispExp::Integer(42),
-        ]);
+        ]));
         assert_eq!(
             eval(&exp, &mut env).unwrap_err(),
             RispError::TypeError(ILLEGAL_TYPE_FOR_ARITHMETIC_OP)
@@ -472,44 +1514,44 @@ mod tests {
     #[test]
     fn multiply_2_or_more() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Multiply)),
             RispExp::Integer(37),
             RispExp::Integer(42),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(37 * 42));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Multiply)),
             RispExp::Integer(37),
             RispExp::Integer(42),
             RispExp::Integer(42),
             RispExp::Integer(42),
-        ]);
+        ]));
         assert_eq!(
             eval(&exp, &mut env).unwrap(),
             RispExp::Integer(37 * 42 * 42 * 42)
         );
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Multiply)),
             RispExp::Integer(37),
             RispExp::Integer(-42),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(37 * (-42)));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Multiply)),
             RispExp::Integer(-37),
             RispExp::Integer(42),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(-37 * 42));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Multiply)),
             RispExp::Float(-37f64),
             RispExp::Integer(42),
-        ]);
+        ]));
         assert_eq!(
             eval(&exp, &mut env).unwrap(),
             RispExp::Float(-37f64 * 42f64)
@@ -519,30 +1561,30 @@ mod tests {
     #[test]
     fn multiply1() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Multiply)),
             RispExp::Integer(37),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(37));
     }
 
     #[test]
     fn multiply0() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![RispExp::Func(RispFunction::Builtin(
+        let exp = RispExp::List(Rc::new([RispExp::Func(RispFunction::Builtin(
             RispBuiltinFunction::Multiply,
-        ))]);
+        ))]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(1));
     }
 
     #[test]
     fn multiply_non_number() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Minus)),
             RispExp::String("Locutus".to_owned()),
             RispExp::Integer(42),
-        ]);
+        ]));
         assert_eq!(
             eval(&exp, &mut env).unwrap_err(),
             RispError::TypeError(ILLEGAL_TYPE_FOR_ARITHMETIC_OP)
@@ -552,44 +1594,47 @@ mod tests {
     #[test]
     fn divide_2_or_more() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Divide)),
             RispExp::Integer(100),
             RispExp::Integer(42),
-        ]);
-        assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(100 / 42));
+        ]));
+        assert_eq!(
+            eval(&exp, &mut env).unwrap(),
+            RispExp::Ratio(Box::new(BigRational::new(50.into(), 21.into())))
+        );
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Divide)),
             RispExp::Integer(1000),
             RispExp::Integer(10),
             RispExp::Integer(10),
             RispExp::Integer(5),
-        ]);
+        ]));
         assert_eq!(
             eval(&exp, &mut env).unwrap(),
             RispExp::Integer(1000 / 10 / 10 / 5)
         );
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Divide)),
             RispExp::Integer(100),
             RispExp::Integer(-50),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(100 / -50));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Divide)),
             RispExp::Integer(-100),
             RispExp::Integer(50),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(-100 / 50));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Divide)),
             RispExp::Float(-1000f64),
             RispExp::Integer(500),
-        ]);
+        ]));
         assert_eq!(
             eval(&exp, &mut env).unwrap(),
             RispExp::Float(-1000f64 / 500f64)
@@ -599,19 +1644,19 @@ mod tests {
     #[test]
     fn divide1() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Divide)),
             RispExp::Integer(37),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(37));
     }
 
     #[test]
     fn divide0() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![RispExp::Func(RispFunction::Builtin(
+        let exp = RispExp::List(Rc::new([RispExp::Func(RispFunction::Builtin(
             RispBuiltinFunction::Divide,
-        ))]);
+        ))]));
         assert_eq!(
             eval(&exp, &mut env).unwrap_err(),
             RispError::ArityMismatch(RispFunction::Builtin(RispBuiltinFunction::Divide))
@@ -621,224 +1666,336 @@ mod tests {
     #[test]
     fn divide_non_number() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Divide)),
             RispExp::String("Locutus".to_owned()),
             RispExp::Integer(42),
-        ]);
+        ]));
         assert_eq!(
             eval(&exp, &mut env).unwrap_err(),
             RispError::TypeError(ILLEGAL_TYPE_FOR_ARITHMETIC_OP)
         );
     }
 
+    #[test]
+    fn arithmetic_at_i64_bounds() {
+        let mut env = RispEnv::default();
+        let exp = RispExp::List(Rc::new([
+            RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
+            RispExp::Integer(i64::MAX - 1),
+            RispExp::Integer(1),
+        ]));
+        assert_eq!(eval(&exp, &mut env), Ok(RispExp::Integer(i64::MAX)));
+
+        let exp = RispExp::List(Rc::new([
+            RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Minus)),
+            RispExp::Integer(i64::MIN + 1),
+            RispExp::Integer(1),
+        ]));
+        assert_eq!(eval(&exp, &mut env), Ok(RispExp::Integer(i64::MIN)));
+
+        let exp = RispExp::List(Rc::new([
+            RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Multiply)),
+            RispExp::Integer(i64::MAX),
+            RispExp::Integer(-1),
+        ]));
+        assert_eq!(eval(&exp, &mut env), Ok(RispExp::Integer(-i64::MAX)));
+
+        let exp = RispExp::List(Rc::new([
+            RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Divide)),
+            RispExp::Integer(i64::MIN),
+            RispExp::Integer(-2),
+        ]));
+        assert_eq!(eval(&exp, &mut env), Ok(RispExp::Integer(i64::MIN / -2)));
+    }
+
+    #[test]
+    fn arithmetic_overflow_promotes_to_bignums() {
+        let mut env = RispEnv::default();
+        let exp = RispExp::List(Rc::new([
+            RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
+            RispExp::Integer(i64::MAX),
+            RispExp::Integer(1),
+        ]));
+        assert_eq!(
+            eval(&exp, &mut env),
+            Ok(RispExp::BigInt(BigInt::from(i64::MAX) + 1))
+        );
+
+        let exp = RispExp::List(Rc::new([
+            RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
+            RispExp::Integer(i64::MAX),
+            RispExp::Integer(1),
+            RispExp::Integer(-1),
+        ]));
+        assert_eq!(eval(&exp, &mut env), Ok(RispExp::Integer(i64::MAX)));
+
+        let exp = RispExp::List(Rc::new([
+            RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Minus)),
+            RispExp::Integer(i64::MIN),
+            RispExp::Integer(1),
+        ]));
+        assert_eq!(
+            eval(&exp, &mut env),
+            Ok(RispExp::BigInt(BigInt::from(i64::MIN) - 1))
+        );
+
+        let exp = RispExp::List(Rc::new([
+            RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Multiply)),
+            RispExp::Integer(i64::MAX),
+            RispExp::Integer(2),
+        ]));
+        assert_eq!(
+            eval(&exp, &mut env),
+            Ok(RispExp::BigInt(BigInt::from(i64::MAX) * 2))
+        );
+
+        let exp = RispExp::List(Rc::new([
+            RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Multiply)),
+            RispExp::Integer(i64::MIN),
+            RispExp::Integer(-1),
+        ]));
+        assert_eq!(
+            eval(&exp, &mut env),
+            Ok(RispExp::BigInt(-BigInt::from(i64::MIN)))
+        );
+
+        let exp = RispExp::List(Rc::new([
+            RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Divide)),
+            RispExp::Integer(i64::MIN),
+            RispExp::Integer(-1),
+        ]));
+        assert_eq!(
+            eval(&exp, &mut env),
+            Ok(RispExp::BigInt(-BigInt::from(i64::MIN)))
+        );
+    }
+
+    #[test]
+    fn divide_by_zero_is_an_error() {
+        let mut env = RispEnv::default();
+        let exp = RispExp::List(Rc::new([
+            RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Divide)),
+            RispExp::Integer(1),
+            RispExp::Integer(0),
+        ]));
+        assert_eq!(eval(&exp, &mut env), Err(RispError::DivisionByZero));
+
+        let exp = RispExp::List(Rc::new([
+            RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Divide)),
+            RispExp::Float(1f64),
+            RispExp::Integer(0),
+        ]));
+        assert_eq!(eval(&exp, &mut env), Ok(RispExp::Float(f64::INFINITY)));
+    }
+
     #[test]
     fn and_2_or_more() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::And)),
             RispExp::Bool(true),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(true));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::And)),
             RispExp::Bool(false),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(false));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::And)),
             RispExp::Bool(true),
             RispExp::Bool(false),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(false));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::And)),
             RispExp::Bool(true),
             RispExp::Bool(true),
             RispExp::Bool(true),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(true));
     }
 
     #[test]
     fn and1() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::And)),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(true));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::And)),
             RispExp::Bool(false),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(false));
     }
 
     #[test]
     fn and0() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![RispExp::Func(RispFunction::Builtin(
+        let exp = RispExp::List(Rc::new([RispExp::Func(RispFunction::Builtin(
             RispBuiltinFunction::And,
-        ))]);
+        ))]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(true));
     }
 
     #[test]
     fn and_non_bool() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::And)),
             RispExp::String("Locutus".to_owned()),
             RispExp::Integer(42),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(true));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::And)),
             RispExp::Nil,
             RispExp::Integer(42),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(false));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::And)),
             RispExp::Integer(0),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(true));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::And)),
             RispExp::Integer(42),
             RispExp::Bool(false),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(false));
     }
 
     #[test]
     fn or_2_or_more() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Or)),
             RispExp::Bool(true),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(true));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Or)),
             RispExp::Bool(false),
             RispExp::Bool(false),
             RispExp::Bool(false),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(false));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Or)),
             RispExp::Bool(true),
             RispExp::Bool(false),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(true));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Or)),
             RispExp::Bool(true),
             RispExp::Bool(true),
             RispExp::Bool(true),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(true));
     }
 
     #[test]
     fn or1() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Or)),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(true));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Or)),
             RispExp::Bool(false),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(false));
     }
 
     #[test]
     fn or0() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![RispExp::Func(RispFunction::Builtin(
+        let exp = RispExp::List(Rc::new([RispExp::Func(RispFunction::Builtin(
             RispBuiltinFunction::Or,
-        ))]);
+        ))]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(false));
     }
 
     #[test]
     fn or_non_bool() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Or)),
             RispExp::String("Locutus".to_owned()),
             RispExp::Integer(42),
             RispExp::Bool(false),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(true));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Or)),
             RispExp::Nil,
             RispExp::Bool(false),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(false));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Or)),
             RispExp::Integer(0),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(true));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Or)),
             RispExp::Integer(42),
             RispExp::Bool(false),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(true));
     }
 
     #[test]
     fn not_2_or_more() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Not)),
             RispExp::Bool(true),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(
             eval(&exp, &mut env).unwrap_err(),
             RispError::ArityMismatch(RispFunction::Builtin(RispBuiltinFunction::Not))
         );
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Not)),
             RispExp::Bool(false),
             RispExp::Bool(false),
             RispExp::Bool(false),
-        ]);
+        ]));
         assert_eq!(
             eval(&exp, &mut env).unwrap_err(),
             RispError::ArityMismatch(RispFunction::Builtin(RispBuiltinFunction::Not))
@@ -848,25 +2005,25 @@ mod tests {
     #[test]
     fn not1() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Not)),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(false));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Not)),
             RispExp::Bool(false),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(true));
     }
 
     #[test]
     fn not0() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![RispExp::Func(RispFunction::Builtin(
+        let exp = RispExp::List(Rc::new([RispExp::Func(RispFunction::Builtin(
             RispBuiltinFunction::Not,
-        ))]);
+        ))]));
         assert_eq!(
             eval(&exp, &mut env).unwrap_err(),
             RispError::ArityMismatch(RispFunction::Builtin(RispBuiltinFunction::Not))
@@ -876,148 +2033,148 @@ mod tests {
     #[test]
     fn not_non_bool() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Not)),
             RispExp::String("Locutus".to_owned()),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(false));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Not)),
             RispExp::Nil,
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(true));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Not)),
             RispExp::Integer(0),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(false));
     }
 
     #[test]
     fn xor_2_or_more() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Xor)),
             RispExp::Bool(true),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(false));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Xor)),
             RispExp::Bool(false),
             RispExp::Bool(false),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(true));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Xor)),
             RispExp::Bool(true),
             RispExp::Bool(false),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(false));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Xor)),
             RispExp::Bool(true),
             RispExp::Bool(true),
             RispExp::Bool(true),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(false));
     }
 
     #[test]
     fn xor1() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Xor)),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(true));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Xor)),
             RispExp::Bool(false),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(false));
     }
 
     #[test]
     fn xor0() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![RispExp::Func(RispFunction::Builtin(
+        let exp = RispExp::List(Rc::new([RispExp::Func(RispFunction::Builtin(
             RispBuiltinFunction::Xor,
-        ))]);
+        ))]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(false));
     }
 
     #[test]
     fn xor_non_bool() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Xor)),
             RispExp::String("Locutus".to_owned()),
             RispExp::Bool(false),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(true));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Xor)),
             RispExp::Nil,
             RispExp::Bool(false),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(false));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Xor)),
             RispExp::Integer(0),
             RispExp::Bool(true),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(false));
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Xor)),
             RispExp::Integer(42),
             RispExp::Bool(false),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Bool(true));
     }
 
     #[test]
     fn nested_builtin_eval() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
             RispExp::Integer(37),
-            RispExp::List(vec![
+            RispExp::List(Rc::new([
                 RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
                 RispExp::Integer(42),
                 RispExp::Integer(100),
-            ]),
-        ]);
+            ])),
+        ]));
         assert_eq!(
             eval(&exp, &mut env).unwrap(),
             RispExp::Integer(37 + 42 + 100)
         );
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
-            RispExp::List(vec![
+            RispExp::List(Rc::new([
                 RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
                 RispExp::Integer(42),
-                RispExp::List(vec![
+                RispExp::List(Rc::new([
                     RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
                     RispExp::Integer(42),
                     RispExp::Integer(100),
-                ]),
-            ]),
+                ])),
+            ])),
             RispExp::Integer(37),
-        ]);
+        ]));
         assert_eq!(
             eval(&exp, &mut env).unwrap(),
             RispExp::Integer(42 + 42 + 100 + 37)
@@ -1027,57 +2184,57 @@ mod tests {
     #[test]
     fn def_works() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Def)),
             RispExp::Symbol("captain".to_owned()),
             RispExp::String("picard".to_owned()),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Nil);
         assert_eq!(
             env.get("captain").unwrap(),
-            &RispExp::String("picard".to_owned())
+            RispExp::String("picard".to_owned())
         );
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Def)),
             RispExp::Symbol("one".to_owned()),
             RispExp::Integer(1),
-        ]);
+        ]));
         eval(&exp, &mut env).unwrap();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Def)),
             RispExp::Symbol("two".to_owned()),
             RispExp::Integer(2),
-        ]);
+        ]));
         eval(&exp, &mut env).unwrap();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
             RispExp::Symbol("one".to_owned()),
             RispExp::Symbol("two".to_owned()),
-        ]);
+        ]));
         assert_eq!(eval(&exp, &mut env).unwrap(), RispExp::Integer(3));
     }
 
     #[test]
     fn if_works() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::If)),
             RispExp::Bool(true),
             RispExp::String("true".to_owned()),
             RispExp::String("false".to_owned()),
-        ]);
+        ]));
         assert_eq!(
             eval(&exp, &mut env).unwrap(),
             RispExp::String("true".to_owned())
         );
 
-        let exp = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::If)),
             RispExp::Bool(false),
             RispExp::String("true".to_owned()),
             RispExp::String("false".to_owned()),
-        ]);
+        ]));
         assert_eq!(
             eval(&exp, &mut env).unwrap(),
             RispExp::String("false".to_owned())
@@ -1087,25 +2244,29 @@ mod tests {
     #[test]
     fn fn_works() {
         let mut env = RispEnv::default();
-        let exp = RispExp::List(vec![RispExp::Lambda(RispFunction::Function {
-            params: Box::new(RispExp::List(vec![RispExp::Symbol("x".to_string())])),
-            body: Box::new(RispExp::List(vec![
-                RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
-                RispExp::Symbol("x".to_string()),
-                RispExp::Integer(1),
-            ])),
-        })]);
-        let def = RispExp::List(vec![
+        let exp = RispExp::List(Rc::new([RispExp::Lambda(RispFunction::Function {
+            clauses: Rc::new([FnClause {
+                params: Rc::new(RispExp::List(Rc::new([RispExp::Symbol("x".to_string())]))),
+                body: Rc::new(RispExp::List(Rc::new([
+                    RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
+                    RispExp::Symbol("x".to_string()),
+                    RispExp::Integer(1),
+                ]))),
+                spec: None,
+            }]),
+            env: None,
+        })]));
+        let def = RispExp::List(Rc::new([
             RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Def)),
             RispExp::Symbol("foo".to_owned()),
             exp,
-        ]);
+        ]));
         assert_eq!(eval(&def, &mut env).unwrap(), RispExp::Nil);
 
-        let invocation = RispExp::List(vec![
+        let invocation = RispExp::List(Rc::new([
             RispExp::Symbol("foo".to_owned()),
             RispExp::Integer(37),
-        ]);
+        ]));
         assert_eq!(eval(&invocation, &mut env).unwrap(), RispExp::Integer(38));
     }
 }
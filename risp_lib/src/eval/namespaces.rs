@@ -0,0 +1,91 @@
+use crate::environment::RispEnv;
+use crate::error::{RispError, RispResult};
+use crate::eval::load_file;
+use crate::parser::{RispExp, RispFunction};
+use crate::symbols_constants::{AS_OPTION_KEYWORD, REFER_ALL_KEYWORD, REFER_OPTION_KEYWORD};
+use std::path::PathBuf;
+
+/// (ns name), switches env to the namespace name, creating it if it doesn't exist. Like def at the
+/// repl it only sticks at the top level, where env is the caller's own env.
+pub(super) fn op_ns(env: &mut RispEnv, rest: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    match rest {
+        [RispExp::Symbol(name)] => {
+            *env = env.namespace_or_create(name);
+            Ok(RispExp::Nil)
+        }
+        [_] => Err(RispError::MalformedNsExpression),
+        _ => Err(RispError::ArityMismatch(f.clone())),
+    }
+}
+
+/// (require module :as alias :refer (name...)), loads module if it isn't already. Its names can
+/// then be used as module/name, alias/name, or just name for the referred ones (:refer :all
+/// refers every name).
+pub(super) fn op_require(env: &mut RispEnv, rest: &[RispExp]) -> RispResult<RispExp> {
+    let (module_name, options) = match rest.split_first() {
+        Some((RispExp::Symbol(name), options)) if options.len() % 2 == 0 => (name, options),
+        _ => return Err(RispError::MalformedRequireExpression),
+    };
+    let module = load_namespace(env, module_name)?;
+
+    for option in options.chunks(2) {
+        match option {
+            [RispExp::Keyword(o), alias] if o == AS_OPTION_KEYWORD => match alias_name(alias) {
+                Some(alias) => env.alias_namespace(&alias, module_name),
+                None => return Err(RispError::MalformedRequireExpression),
+            },
+            [RispExp::Keyword(o), RispExp::Keyword(all)]
+                if o == REFER_OPTION_KEYWORD && all == REFER_ALL_KEYWORD =>
+            {
+                for (name, value) in module.bindings() {
+                    env.def(&name, &value)?;
+                }
+            }
+            [RispExp::Keyword(o), RispExp::List(names)] if o == REFER_OPTION_KEYWORD => {
+                for name in names.iter() {
+                    match name {
+                        RispExp::Symbol(name) => env.def(name, &module.get(name)?)?,
+                        _ => return Err(RispError::MalformedRequireExpression),
+                    };
+                }
+            }
+            _ => return Err(RispError::MalformedRequireExpression),
+        }
+    }
+    Ok(RispExp::Nil)
+}
+
+/// An alias is only ever used as the alias/ part of a name, so it can be the name of a builtin,
+/// which the parser reads as the builtin rather than a symbol.
+fn alias_name(alias: &RispExp) -> Option<String> {
+    match alias {
+        RispExp::Symbol(alias) => Some(alias.clone()),
+        RispExp::Func(builtin @ RispFunction::Builtin(_)) => Some(builtin.to_string()),
+        _ => None,
+    }
+}
+
+/// The root env of the namespace name, loading it from name.risp on the search path if needed.
+/// Dots in the name are directories, so my.lib is my/lib.risp.
+fn load_namespace(env: &RispEnv, name: &str) -> RispResult<RispEnv> {
+    if let Some(namespace) = env.namespace(name) {
+        return Ok(namespace);
+    }
+
+    let relative_path = PathBuf::from(format!("{}.risp", name.replace('.', "/")));
+    let path = env
+        .search_path()
+        .into_iter()
+        .map(|dir| dir.join(&relative_path))
+        .find(|path| path.is_file())
+        .ok_or_else(|| RispError::ModuleNotFound(name.to_owned()))?;
+
+    // The namespace is registered before its forms run so a module that requires itself back
+    // doesn't loop forever.
+    let mut namespace = env.namespace_or_create(name);
+    if let Err(e) = load_file(&path, &mut namespace) {
+        env.remove_namespace(name);
+        return Err(e);
+    }
+    Ok(env.namespace_or_create(name))
+}
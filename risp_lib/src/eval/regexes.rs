@@ -0,0 +1,84 @@
+use crate::error::{RispError, RispResult, EXPECTED_REGEX};
+use crate::eval::expr_to_str;
+use crate::parser::{RispExp, RispFunction, RispRegex};
+use regex::{Captures, Regex};
+
+fn expr_to_regex(arg: &RispExp) -> RispResult<&RispRegex> {
+    match arg {
+        RispExp::Regex(r) => Ok(r),
+        _ => Err(RispError::TypeError(EXPECTED_REGEX)),
+    }
+}
+
+/// Splits (op re s) args into the regex and the string it is matched against.
+fn regex_and_string<'a>(
+    args: &'a [RispExp],
+    f: &RispFunction,
+) -> RispResult<(&'a RispRegex, &'a str)> {
+    match args {
+        [re, s] => Ok((expr_to_regex(re)?, expr_to_str(s)?)),
+        _ => Err(RispError::ArityMismatch(f.clone())),
+    }
+}
+
+/// The value of a match, the matched string when the regex has no groups, otherwise a vector of
+/// the matched string followed by every group with nil for the ones that didn't take part.
+fn match_value(re: &Regex, captures: &Captures) -> RispExp {
+    if re.captures_len() == 1 {
+        return RispExp::String(captures[0].to_owned());
+    }
+    RispExp::Vector(
+        captures
+            .iter()
+            .map(|group| match group {
+                Some(m) => RispExp::String(m.as_str().to_owned()),
+                None => RispExp::Nil,
+            })
+            .collect(),
+    )
+}
+
+/// (re-find re s), the first match of re in s, or nil if there isn't one.
+pub(super) fn op_re_find(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    let (re, s) = regex_and_string(args, f)?;
+    Ok(match re.regex().captures(s) {
+        Some(captures) => match_value(re.regex(), &captures),
+        None => RispExp::Nil,
+    })
+}
+
+/// (re-matches re s), the match of re when it matches the whole of s, or nil if it doesn't.
+pub(super) fn op_re_matches(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    let (re, s) = regex_and_string(args, f)?;
+    // Anchoring the pattern rather than checking the bounds of a match, since the leftmost match
+    // can be shorter than one that covers the whole string.
+    Ok(match re.anchored().captures(s) {
+        Some(captures) => match_value(re.regex(), &captures),
+        None => RispExp::Nil,
+    })
+}
+
+/// (re-seq re s), a list of every match of re in s.
+pub(super) fn op_re_seq(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    let (re, s) = regex_and_string(args, f)?;
+    Ok(RispExp::List(
+        re.regex()
+            .captures_iter(s)
+            .map(|captures| match_value(re.regex(), &captures))
+            .collect(),
+    ))
+}
+
+/// (re-replace re s replacement), s with every match of re replaced. The replacement can refer to
+/// groups with $1 or ${name}.
+pub(super) fn op_re_replace(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    match args {
+        [re, s, replacement] => Ok(RispExp::String(
+            expr_to_regex(re)?
+                .regex()
+                .replace_all(expr_to_str(s)?, expr_to_str(replacement)?)
+                .into_owned(),
+        )),
+        _ => Err(RispError::ArityMismatch(f.clone())),
+    }
+}
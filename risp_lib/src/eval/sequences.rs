@@ -0,0 +1,203 @@
+use crate::environment::RispEnv;
+use crate::error::{RispError, RispResult, EXPECTED_SEQUENCE, RANGE_STEP_IS_ZERO};
+use crate::eval::{apply, expr_to_int, keyword_lookup, truthiness};
+use crate::parser::{RispExp, RispFunction};
+use std::rc::Rc;
+
+type SeqIter<'a> = Box<dyn Iterator<Item = RispExp> + 'a>;
+
+/// The items of a sequence. Lists, vectors and sets are their items, maps are [key value] vectors,
+/// strings are their chars and nil is empty.
+pub(super) fn seq_iter(exp: &RispExp) -> RispResult<SeqIter<'_>> {
+    match exp {
+        RispExp::Nil => Ok(Box::new(std::iter::empty())),
+        RispExp::List(items) | RispExp::Vector(items) => Ok(Box::new(items.iter().cloned())),
+        RispExp::Set(set) => Ok(Box::new(set.iter().cloned())),
+        RispExp::Map(map) => {
+            Ok(Box::new(map.iter().map(|(k, v)| {
+                RispExp::Vector(Rc::new([k.clone(), v.clone()]))
+            })))
+        }
+        RispExp::String(s) => Ok(Box::new(s.chars().map(RispExp::Char))),
+        _ => Err(RispError::TypeError(EXPECTED_SEQUENCE)),
+    }
+}
+
+/// Calls f with args the way a form in call position would, so keywords work as well as functions.
+fn call(f: &RispExp, args: &[RispExp], env: &mut RispEnv) -> RispResult<RispExp> {
+    match f {
+        RispExp::Func(f) => apply(f, args, env),
+        RispExp::Keyword(_) => keyword_lookup(f, args),
+        _ => Err(RispError::FirstFormMustBeFunction(f.clone())),
+    }
+}
+
+/// (cons x seq), a list of x followed by the items of seq.
+pub(super) fn op_cons(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    match args {
+        [x, seq] => Ok(RispExp::List(
+            std::iter::once(x.clone()).chain(seq_iter(seq)?).collect(),
+        )),
+        _ => Err(RispError::ArityMismatch(f.clone())),
+    }
+}
+
+/// (first seq), the first item of seq or nil if it is empty.
+pub(super) fn op_first(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    match args {
+        [seq] => Ok(seq_iter(seq)?.next().unwrap_or(RispExp::Nil)),
+        _ => Err(RispError::ArityMismatch(f.clone())),
+    }
+}
+
+/// (rest seq), a list of every item after the first, empty if there are none.
+pub(super) fn op_rest(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    match args {
+        [seq] => Ok(RispExp::List(seq_iter(seq)?.skip(1).collect())),
+        _ => Err(RispError::ArityMismatch(f.clone())),
+    }
+}
+
+/// (nth seq index default), the item at index counting from 0. An index out of range gives default,
+/// or is an error when there isn't one.
+pub(super) fn op_nth(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    let (seq, index, default) = match args {
+        [seq, index] => (seq, index, None),
+        [seq, index, default] => (seq, index, Some(default)),
+        _ => return Err(RispError::ArityMismatch(f.clone())),
+    };
+    let index = expr_to_int(index)?;
+    let item = match usize::try_from(index) {
+        Ok(i) => seq_iter(seq)?.nth(i),
+        Err(_) => None,
+    };
+    match (item, default) {
+        (Some(item), _) => Ok(item),
+        (None, Some(default)) => Ok(default.clone()),
+        (None, None) => Err(RispError::IndexOutOfBounds(index, seq_iter(seq)?.count())),
+    }
+}
+
+pub(super) fn op_count(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    match args {
+        [seq] => Ok(RispExp::Integer(seq_iter(seq)?.count() as i64)),
+        _ => Err(RispError::ArityMismatch(f.clone())),
+    }
+}
+
+pub(super) fn op_is_empty(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    match args {
+        [seq] => Ok(RispExp::Bool(seq_iter(seq)?.next().is_none())),
+        _ => Err(RispError::ArityMismatch(f.clone())),
+    }
+}
+
+/// (map f seq...), a list of f called on the first items of every seq, then the second items and so
+/// on until the shortest seq runs out.
+pub(super) fn op_map(env: &mut RispEnv, args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    let (map_fn, seqs) = match args.split_first() {
+        Some((map_fn, seqs)) if !seqs.is_empty() => (map_fn, seqs),
+        _ => return Err(RispError::ArityMismatch(f.clone())),
+    };
+    let mut seqs = seqs.iter().map(seq_iter).collect::<RispResult<Vec<_>>>()?;
+    let mut result = vec![];
+    while let Some(call_args) = seqs
+        .iter_mut()
+        .map(Iterator::next)
+        .collect::<Option<Vec<_>>>()
+    {
+        result.push(call(map_fn, &call_args, env)?);
+    }
+    Ok(RispExp::List(result.into()))
+}
+
+/// (filter pred seq), a list of the items of seq that pred is truthy for.
+pub(super) fn op_filter(
+    env: &mut RispEnv,
+    args: &[RispExp],
+    f: &RispFunction,
+) -> RispResult<RispExp> {
+    let (pred, seq) = match args {
+        [pred, seq] => (pred, seq),
+        _ => return Err(RispError::ArityMismatch(f.clone())),
+    };
+    let mut result = vec![];
+    for item in seq_iter(seq)? {
+        if truthiness(&call(pred, std::slice::from_ref(&item), env)?) {
+            result.push(item);
+        }
+    }
+    Ok(RispExp::List(result.into()))
+}
+
+/// (reduce f init seq), folds the items of seq into init with (f acc item). Without init the first
+/// item is used, and an empty seq gives (f).
+pub(super) fn op_reduce(
+    env: &mut RispEnv,
+    args: &[RispExp],
+    f: &RispFunction,
+) -> RispResult<RispExp> {
+    let (reduce_fn, init, seq) = match args {
+        [reduce_fn, seq] => (reduce_fn, None, seq),
+        [reduce_fn, init, seq] => (reduce_fn, Some(init.clone()), seq),
+        _ => return Err(RispError::ArityMismatch(f.clone())),
+    };
+    let mut items = seq_iter(seq)?;
+    let mut acc = match init.or_else(|| items.next()) {
+        Some(acc) => acc,
+        None => return call(reduce_fn, &[], env),
+    };
+    for item in items {
+        acc = call(reduce_fn, &[acc, item], env)?;
+    }
+    Ok(acc)
+}
+
+/// (concat seq...), a list of the items of every seq in order.
+pub(super) fn op_concat(args: &[RispExp]) -> RispResult<RispExp> {
+    let mut result = vec![];
+    for seq in args {
+        result.extend(seq_iter(seq)?);
+    }
+    Ok(RispExp::List(result.into()))
+}
+
+pub(super) fn op_reverse(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    match args {
+        [seq] => {
+            let mut items = seq_iter(seq)?.collect::<Vec<_>>();
+            items.reverse();
+            Ok(RispExp::List(items.into()))
+        }
+        _ => Err(RispError::ArityMismatch(f.clone())),
+    }
+}
+
+/// (range end), (range start end) or (range start end step), a list of the integers from start, which
+/// defaults to 0, up to but not including end.
+pub(super) fn op_range(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    let bounds = args
+        .iter()
+        .map(expr_to_int)
+        .collect::<RispResult<Vec<_>>>()?;
+    let (start, end, step) = match bounds[..] {
+        [end] => (0, end, 1),
+        [start, end] => (start, end, 1),
+        [start, end, step] => (start, end, step),
+        _ => return Err(RispError::ArityMismatch(f.clone())),
+    };
+    if step == 0 {
+        return Err(RispError::UnexpectedExpr(
+            args[2].clone(),
+            RANGE_STEP_IS_ZERO.to_owned(),
+        ));
+    }
+
+    let mut result = vec![];
+    let mut i = Some(start);
+    while let Some(n) = i.filter(|n| if step > 0 { *n < end } else { *n > end }) {
+        result.push(RispExp::Integer(n));
+        i = n.checked_add(step);
+    }
+    Ok(RispExp::List(result.into()))
+}
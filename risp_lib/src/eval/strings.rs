@@ -0,0 +1,140 @@
+use crate::error::{RispError, RispResult};
+use crate::eval::sequences::seq_iter;
+use crate::eval::{expr_to_int, expr_to_str};
+use crate::parser::{RispExp, RispFunction};
+
+// Strings are indexed and measured in chars, not bytes, so multi byte characters count as one.
+
+/// How str and join turn a value into text, strings and chars as they are, symbols as their bare
+/// name and nil as nothing.
+fn to_text(exp: &RispExp) -> String {
+    match exp {
+        RispExp::Nil => "".to_owned(),
+        RispExp::Char(c) => c.to_string(),
+        RispExp::Symbol(s) => s.clone(),
+        _ => exp.to_string(),
+    }
+}
+
+/// Applies op to the only arg, which must be a string.
+fn map_string(
+    args: &[RispExp],
+    f: &RispFunction,
+    op: impl Fn(&str) -> RispExp,
+) -> RispResult<RispExp> {
+    match args {
+        [s] => Ok(op(expr_to_str(s)?)),
+        _ => Err(RispError::ArityMismatch(f.clone())),
+    }
+}
+
+/// (str x...), the text of every arg joined together.
+pub(super) fn op_str(args: &[RispExp]) -> RispResult<RispExp> {
+    Ok(RispExp::String(args.iter().map(to_text).collect()))
+}
+
+/// (subs s start end), the chars of s from start up to but not including end, which defaults to
+/// the end of s.
+pub(super) fn op_subs(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    let (s, start, end) = match args {
+        [s, start] => (expr_to_str(s)?, expr_to_int(start)?, None),
+        [s, start, end] => (
+            expr_to_str(s)?,
+            expr_to_int(start)?,
+            Some(expr_to_int(end)?),
+        ),
+        _ => return Err(RispError::ArityMismatch(f.clone())),
+    };
+    let chars = s.chars().collect::<Vec<_>>();
+    let len = chars.len();
+    let end = end.unwrap_or(len as i64);
+    let index = |i: i64, max: usize| usize::try_from(i).ok().filter(|i| *i <= max);
+    let end_index = index(end, len).ok_or(RispError::IndexOutOfBounds(end, len))?;
+    let start_index = index(start, len).ok_or(RispError::IndexOutOfBounds(start, len))?;
+    if start_index > end_index {
+        return Err(RispError::StartAfterEnd(start, end));
+    }
+    Ok(RispExp::String(
+        chars[start_index..end_index].iter().collect(),
+    ))
+}
+
+pub(super) fn op_str_len(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    map_string(args, f, |s| RispExp::Integer(s.chars().count() as i64))
+}
+
+pub(super) fn op_upper(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    map_string(args, f, |s| RispExp::String(s.to_uppercase()))
+}
+
+pub(super) fn op_lower(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    map_string(args, f, |s| RispExp::String(s.to_lowercase()))
+}
+
+/// (trim s), s without leading or trailing whitespace.
+pub(super) fn op_trim(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    map_string(args, f, |s| RispExp::String(s.trim().to_owned()))
+}
+
+/// (split s separator), a list of the pieces of s between each separator. An empty separator
+/// splits s into its chars.
+pub(super) fn op_split(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    let (s, separator) = match args {
+        [s, separator] => (expr_to_str(s)?, expr_to_str(separator)?),
+        _ => return Err(RispError::ArityMismatch(f.clone())),
+    };
+    let pieces = if separator.is_empty() {
+        s.chars().map(|c| RispExp::String(c.to_string())).collect()
+    } else {
+        s.split(separator)
+            .map(|piece| RispExp::String(piece.to_owned()))
+            .collect()
+    };
+    Ok(RispExp::List(pieces))
+}
+
+/// (join seq) or (join separator seq), the text of every item of seq with separator between them.
+pub(super) fn op_join(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    let (separator, seq) = match args {
+        [seq] => ("", seq),
+        [separator, seq] => (expr_to_str(separator)?, seq),
+        _ => return Err(RispError::ArityMismatch(f.clone())),
+    };
+    Ok(RispExp::String(
+        seq_iter(seq)?
+            .map(|item| to_text(&item))
+            .collect::<Vec<_>>()
+            .join(separator),
+    ))
+}
+
+pub(super) fn op_starts_with(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    match args {
+        [s, prefix] => Ok(RispExp::Bool(
+            expr_to_str(s)?.starts_with(expr_to_str(prefix)?),
+        )),
+        _ => Err(RispError::ArityMismatch(f.clone())),
+    }
+}
+
+/// (index-of s sub), the char index of the first sub in s, or nil if it isn't there.
+pub(super) fn op_index_of(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    let (s, sub) = match args {
+        [s, sub] => (expr_to_str(s)?, expr_to_str(sub)?),
+        _ => return Err(RispError::ArityMismatch(f.clone())),
+    };
+    Ok(match s.find(sub) {
+        Some(byte_index) => RispExp::Integer(s[..byte_index].chars().count() as i64),
+        None => RispExp::Nil,
+    })
+}
+
+/// (replace s from to), s with every from replaced by to.
+pub(super) fn op_replace(args: &[RispExp], f: &RispFunction) -> RispResult<RispExp> {
+    match args {
+        [s, from, to] => Ok(RispExp::String(
+            expr_to_str(s)?.replace(expr_to_str(from)?, expr_to_str(to)?),
+        )),
+        _ => Err(RispError::ArityMismatch(f.clone())),
+    }
+}
@@ -1,82 +1,227 @@
+use indexmap::{IndexMap, IndexSet};
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, ToPrimitive};
+use regex::Regex;
+use std::cell::RefCell;
 use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
 use std::fmt::{Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
 
+use crate::environment::RispEnv;
 use crate::error::{
-    RispError, RispResult, EXPECTED_ARGS_LIST_FOR_FN, EXPECTED_FN_DEF_FOR_FN, TRAILING_TOKENS,
-    UNEXPECTED_CLOSING_PAREN,
+    RispError, RispResult, EXPECTED_ARGS_LIST_FOR_FN, EXPECTED_FN_DEF_FOR_FN, MALFORMED_FN_CLAUSE,
+    MAP_LITERAL_NEEDS_PAIRS, TRAILING_TOKENS, UNEXPECTED_CLOSING_PAREN,
 };
+use crate::eval::ParamSpec;
 use crate::parser::RispFunction::Builtin;
 use crate::symbols_constants::{
-    AND_SYM, DEF_SYM, DIV_SYM, EQ_SYM, GTE_SYM, GT_SYM, IF_SYM, LAMBDA_SYM, LTE_SYM, LT_SYM,
-    MINUS_SYM, MULTIPLY_SYM, NOT_SYM, OR_SYM, PLUS_SYM, XOR_SYM,
+    AND_SYM, ATOM_SYM, CASE_SYM, CONCAT_SYM, COND_SYM, CONS_SYM, COUNT_SYM, DEFMACRO_SYM, DEF_SYM,
+    DEREF_SYM, DIV_SYM, DO_SYM, EQ_SYM, FILTER_SYM, FIRST_SYM, GTE_SYM, GT_SYM, IF_SYM,
+    INDEX_OF_SYM, IS_EMPTY_SYM, JOIN_SYM, LAMBDA_SYM, LETREC_SYM, LET_STAR_SYM, LET_SYM, LOOP_SYM,
+    LOWER_SYM, LTE_SYM, LT_SYM, MACROEXPAND_1_SYM, MACROEXPAND_SYM, MAP_SYM, MATCH_SYM, MINUS_SYM,
+    MULTIPLY_SYM, NOT_SYM, NS_SYM, NTH_SYM, OR_SYM, PLUS_SYM, QUASIQUOTE_SYM, QUOTE_SYM, RANGE_SYM,
+    RECUR_SYM, REDUCE_SYM, REPLACE_SYM, REQUIRE_SYM, RESET_SYM, REST_SYM, REVERSE_SYM, RE_FIND_SYM,
+    RE_MATCHES_SYM, RE_REPLACE_SYM, RE_SEQ_SYM, SET_SYM, SPLIT_SYM, STARTS_WITH_SYM, STR_LEN_SYM,
+    STR_SYM, SUBS_SYM, SWAP_SYM, THROW_SYM, TRIM_SYM, TRY_SYM, UNQUOTE_SPLICING_SYM, UNQUOTE_SYM,
+    UPPER_SYM, XOR_SYM,
 };
-use crate::tokenizer::{ComparisonOp, RispToken};
+use crate::tokenizer::{ComparisonOp, Position, RispToken};
 
 pub fn parse(tokens: &[RispToken]) -> RispResult<RispExp> {
-    let result = parse_internal(tokens)?;
-    if result.1.len() > 0 {
+    let (exp, rest) = parse_internal(tokens)?;
+    if let Some(trailing) = rest.first() {
         return Err(RispError::UnexpectedToken(
-            result.1.get(0).unwrap().clone(),
+            trailing.clone(),
             TRAILING_TOKENS.to_owned(),
         ));
     }
-    Ok(result.0)
+    Ok(exp)
 }
 
-fn parse_internal<'a>(tokens: &[RispToken]) -> RispResult<(RispExp, &[RispToken])> {
-    if tokens.len() == 0 {
-        return Ok((RispExp::Empty, &[]));
+/// Parses every top level form in tokens, for whole files or a repl line with several forms, along
+/// with the position each form starts at. positions has the position of every token, as given by
+/// [crate::tokenizer::tokenize_with_positions]. An error is wrapped in [RispError::InForm] with the
+/// position of the form it is in.
+pub fn parse_program(
+    tokens: &[RispToken],
+    positions: &[Position],
+) -> RispResult<Vec<(RispExp, Position)>> {
+    let mut forms = vec![];
+    let mut rest = tokens;
+    while !rest.is_empty() {
+        let position = positions[tokens.len() - rest.len()];
+        let (exp, new_rest) =
+            parse_internal(rest).map_err(|e| RispError::InForm(position, Box::new(e)))?;
+        forms.push((exp, position));
+        rest = new_rest;
     }
-    let (token, rest) = tokens.split_first().unwrap();
-    match &token {
+    Ok(forms)
+}
+
+fn parse_internal(tokens: &[RispToken]) -> RispResult<(RispExp, &[RispToken])> {
+    let (token, rest) = match tokens.split_first() {
+        Some(split) => split,
+        None => return Ok((RispExp::Empty, &[])),
+    };
+    match token {
         RispToken::Fn => read_fn(rest),
+        RispToken::Quote => read_reader_macro(RispBuiltinFunction::Quote, rest),
+        RispToken::Quasiquote => read_reader_macro(RispBuiltinFunction::Quasiquote, rest),
+        RispToken::Unquote => read_reader_macro(RispBuiltinFunction::Unquote, rest),
+        RispToken::UnquoteSplicing => read_reader_macro(RispBuiltinFunction::UnquoteSplicing, rest),
         RispToken::LParen => read_seq(rest),
-        RispToken::RParen => Err(RispError::UnexpectedToken(
-            token.clone(),
-            UNEXPECTED_CLOSING_PAREN.to_owned(),
-        )),
+        RispToken::LBracket => read_vector(rest),
+        RispToken::LBrace => read_map(rest),
+        RispToken::SetLBrace => read_set(rest),
+        RispToken::RParen | RispToken::RBracket | RispToken::RBrace => Err(
+            RispError::UnexpectedToken(token.clone(), UNEXPECTED_CLOSING_PAREN.to_owned()),
+        ),
         _ => Ok((parse_atom(token)?, rest)),
     }
 }
 
+/// Reads either (fn (params) body...) or a multi-arity (fn ([params] body...) ...), which is
+/// recognised by the first form after fn being a list that starts with a params vector. The params
+/// of a single-arity fn can be written as a vector too, eg (fn [[a b] c] ...).
 fn read_fn(rest: &[RispToken]) -> RispResult<(RispExp, &[RispToken])> {
     let (args_list_begin, rest_args) = rest.split_first().ok_or(RispError::UnterminatedList)?;
     let (args, rest_fn) = match args_list_begin {
         RispToken::LParen => read_seq(rest_args)?,
+        RispToken::LBracket => read_vector(rest_args)?,
         _ => Err(RispError::UnexpectedToken(
             args_list_begin.clone(),
             EXPECTED_ARGS_LIST_FOR_FN.to_owned(),
         ))?,
     };
-    let (function_def_begin, rest_fn_def) =
-        rest_fn.split_first().ok_or(RispError::UnterminatedList)?;
-    let (body, rest_done) = match function_def_begin {
-        RispToken::LParen => read_seq(rest_fn_def)?,
-        _ => Err(RispError::UnexpectedToken(
-            args_list_begin.clone(),
-            EXPECTED_FN_DEF_FOR_FN.to_owned(),
-        ))?,
-    };
+
+    // The body is every form up to the paren closing the fn.
+    let mut body = vec![];
+    let mut rest_done = rest_fn;
+    while let Some(next_token) = rest_done.first() {
+        if next_token == &RispToken::RParen {
+            break;
+        }
+        let (exp, new_rest) = parse_internal(rest_done)?;
+        body.push(exp);
+        rest_done = new_rest;
+    }
+    if matches!(&args, RispExp::List(items) if starts_with_vector(items)) {
+        let clauses = std::iter::once(args)
+            .chain(body)
+            .map(|form| match form {
+                RispExp::List(clause) if clause.len() > 1 && starts_with_vector(&clause) => {
+                    Ok(FnClause::new(clause[0].clone(), clause[1..].to_vec()))
+                }
+                _ => Err(RispError::UnexpectedExpr(
+                    form,
+                    MALFORMED_FN_CLAUSE.to_owned(),
+                )),
+            })
+            .collect::<RispResult<Vec<FnClause>>>()?;
+        return Ok((
+            RispExp::Lambda(RispFunction::Function {
+                clauses: clauses.into(),
+                env: None,
+            }),
+            rest_done,
+        ));
+    }
+    if body.is_empty() {
+        return match rest_done.first() {
+            Some(token) => Err(RispError::UnexpectedToken(
+                token.clone(),
+                EXPECTED_FN_DEF_FOR_FN.to_owned(),
+            )),
+            None => Err(RispError::UnterminatedList),
+        };
+    }
 
     Ok((
         RispExp::Lambda(RispFunction::Function {
-            params: Box::new(args.clone()),
-            body: Box::new(body.clone()),
+            clauses: Rc::new([FnClause::new(args, body)]),
+            env: None,
         }),
         rest_done,
     ))
 }
 
+fn starts_with_vector(items: &[RispExp]) -> bool {
+    matches!(items.first(), Some(RispExp::Vector(_)))
+}
+
+/// Turns a body of several forms into a single (do ...) form, a body of one form is left as is.
+pub(crate) fn implicit_do(mut body: Vec<RispExp>) -> RispExp {
+    if body.len() == 1 {
+        body.remove(0)
+    } else {
+        body.insert(0, RispExp::Func(Builtin(RispBuiltinFunction::Do)));
+        RispExp::List(body.into())
+    }
+}
+
+/// Lowers a reader macro into the form it stands for, eg 'x becomes (quote x).
+fn read_reader_macro(
+    builtin: RispBuiltinFunction,
+    rest: &[RispToken],
+) -> RispResult<(RispExp, &[RispToken])> {
+    if rest.is_empty() {
+        return Err(RispError::UnexpectedEndOfInput);
+    }
+    let (exp, rest) = parse_internal(rest)?;
+    Ok((
+        RispExp::List(Rc::new([RispExp::Func(Builtin(builtin)), exp])),
+        rest,
+    ))
+}
+
 fn read_seq(rest: &[RispToken]) -> RispResult<(RispExp, &[RispToken])> {
+    let (items, rest) = read_until(rest, &RispToken::RParen)?;
+    Ok((RispExp::List(items.into()), rest))
+}
+
+fn read_vector(rest: &[RispToken]) -> RispResult<(RispExp, &[RispToken])> {
+    let (items, rest) = read_until(rest, &RispToken::RBracket)?;
+    Ok((RispExp::Vector(items.into()), rest))
+}
+
+/// Reads {key value ...}, a key written twice keeps its last value.
+fn read_map(rest: &[RispToken]) -> RispResult<(RispExp, &[RispToken])> {
+    let (items, rest) = read_until(rest, &RispToken::RBrace)?;
+    if !items.len().is_multiple_of(2) {
+        return Err(RispError::UnexpectedToken(
+            RispToken::RBrace,
+            MAP_LITERAL_NEEDS_PAIRS.to_owned(),
+        ));
+    }
+    let map = items
+        .chunks_exact(2)
+        .map(|pair| (pair[0].clone(), pair[1].clone()))
+        .collect();
+    Ok((RispExp::Map(Box::new(map)), rest))
+}
+
+fn read_set(rest: &[RispToken]) -> RispResult<(RispExp, &[RispToken])> {
+    let (items, rest) = read_until(rest, &RispToken::RBrace)?;
+    Ok((RispExp::Set(Box::new(items.into_iter().collect())), rest))
+}
+
+/// Reads forms up to the close token that ends the collection they are in.
+fn read_until<'a>(
+    rest: &'a [RispToken],
+    close: &RispToken,
+) -> RispResult<(Vec<RispExp>, &'a [RispToken])> {
     let mut res: Vec<RispExp> = vec![];
     let mut xs = rest;
     loop {
         let (next_token, rest) = xs.split_first().ok_or(RispError::UnterminatedList)?;
-        if next_token == &RispToken::RParen {
-            return Ok((RispExp::List(res), rest));
+        if next_token == close {
+            return Ok((res, rest));
         }
 
-        let (exp, new_xs) = parse_internal(&xs)?;
+        let (exp, new_xs) = parse_internal(xs)?;
         res.push(exp);
         xs = new_xs;
     }
@@ -88,19 +233,32 @@ fn parse_atom(token: &RispToken) -> RispResult<RispExp> {
         RispToken::Char(c) => Ok(RispExp::Char(*c)),
         RispToken::Bool(b) => Ok(RispExp::Bool(*b)),
         RispToken::Integer(i) => Ok(RispExp::Integer(*i)),
+        RispToken::BigInt(i) => Ok(i.clone().into()),
+        RispToken::Ratio(r) => Ok(r.clone().into()),
         RispToken::Float(f) => Ok(RispExp::Float(*f)),
 
         RispToken::Symbol(str) => parse_symbol(str),
+        RispToken::Keyword(name) => Ok(RispExp::Keyword(name.to_owned())),
         RispToken::StringLiteral(str) => Ok(RispExp::String(str.to_owned())),
+        RispToken::RegexLiteral(pattern) => Ok(RispExp::Regex(RispRegex::new(pattern)?)),
 
         RispToken::Comparison(cmp) => Ok(cmp.into()),
 
         RispToken::Def => Ok(RispExp::Func(Builtin(RispBuiltinFunction::Def))),
         RispToken::If => Ok(RispExp::Func(Builtin(RispBuiltinFunction::If))),
 
-        t @ (RispToken::LParen | RispToken::RParen | RispToken::Fn) => {
-            Err(RispError::UnexpectedToken(t.clone(), "".to_owned()))
-        }
+        t @ (RispToken::LParen
+        | RispToken::RParen
+        | RispToken::LBracket
+        | RispToken::RBracket
+        | RispToken::LBrace
+        | RispToken::RBrace
+        | RispToken::SetLBrace
+        | RispToken::Fn
+        | RispToken::Quote
+        | RispToken::Quasiquote
+        | RispToken::Unquote
+        | RispToken::UnquoteSplicing) => Err(RispError::UnexpectedToken(t.clone(), "".to_owned())),
     }
 }
 
@@ -116,20 +274,112 @@ pub enum RispExp {
     Empty, // Used to signify empty input
     Nil,
     Symbol(String),
+    // :name, evaluates to itself and is equal to every other keyword with the same name.
+    Keyword(String),
     Bool(bool),
-    Integer(i32),
+    Integer(i64),
+    // Integer too big for Integer, arithmetic promotes to it on overflow.
+    BigInt(BigInt),
+    // Exact fraction in lowest terms, integer / makes these. Boxed to keep RispExp small.
+    Ratio(Box<BigRational>),
     Float(f64),
     Char(char),
 
     String(String),
+    // #"pattern", compiled when it is read so a bad pattern is a parse error.
+    Regex(RispRegex),
 
-    List(Vec<RispExp>),
+    // Shared so that handing a form on, as a tail call does, doesn't copy it.
+    List(Rc<[RispExp]>),
+    // [a b], unlike a list it isn't a call when evaluated, its items are evaluated.
+    Vector(Rc<[RispExp]>),
+    // {key value ...} and #{item ...}, boxed to keep RispExp small.
+    Map(Box<RispMap>),
+    Set(Box<RispSet>),
 
     // Function that shall be evaluated. For use when looking up symbols and evaluating
     Func(RispFunction),
 
     // Function literal, for use when parsing and defining symbols.
     Lambda(RispFunction),
+
+    // Transformer from code to code, called on the unevaluated args of a form that names it.
+    Macro(RispFunction),
+
+    // Mutable reference cell, see [RispAtom].
+    Atom(RispAtom),
+}
+
+/// Maps and sets keep the order their entries were added in, but compare equal whatever the order.
+pub type RispMap = IndexMap<RispExp, RispExp>;
+pub type RispSet = IndexSet<RispExp>;
+
+/// A mutable reference cell made with (atom x). Cloning an atom shares the cell, so closures that
+/// hold the same atom see each others' updates.
+#[derive(Clone, Debug)]
+pub struct RispAtom(Rc<RefCell<RispExp>>);
+
+impl RispAtom {
+    pub fn new(exp: RispExp) -> RispAtom {
+        RispAtom(Rc::new(RefCell::new(exp)))
+    }
+
+    pub fn value(&self) -> RispExp {
+        self.0.borrow().clone()
+    }
+
+    pub fn reset(&self, exp: RispExp) -> RispExp {
+        self.0.replace(exp.clone());
+        exp
+    }
+}
+
+/// Two atoms are equal when they are the same cell, like envs.
+impl PartialEq for RispAtom {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// A compiled regex made with #"pattern".
+#[derive(Clone, Debug)]
+pub struct RispRegex {
+    regex: Regex,
+    // The pattern matching only the whole of a string, compiled along with it so re-matches
+    // doesn't compile it on every call. Shared to keep RispExp small.
+    anchored: Rc<Regex>,
+}
+
+impl RispRegex {
+    pub fn new(pattern: &str) -> RispResult<RispRegex> {
+        let compile = |p: &str| {
+            Regex::new(p).map_err(|e| RispError::InvalidRegex(pattern.to_owned(), e.to_string()))
+        };
+        Ok(RispRegex {
+            regex: compile(pattern)?,
+            anchored: Rc::new(compile(&format!(r"\A(?:{})\z", pattern))?),
+        })
+    }
+
+    pub fn regex(&self) -> &Regex {
+        &self.regex
+    }
+
+    /// The regex anchored at both ends, it only matches the whole of a string.
+    pub fn anchored(&self) -> &Regex {
+        &self.anchored
+    }
+
+    pub fn pattern(&self) -> &str {
+        self.regex.as_str()
+    }
+}
+
+/// Two regexes are equal when they were written with the same pattern.
+impl PartialEq for RispRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern() == other.pattern()
+    }
 }
 
 impl From<&ComparisonOp> for RispExp {
@@ -144,18 +394,115 @@ impl From<&ComparisonOp> for RispExp {
     }
 }
 
+/// Demotes to an Integer when it fits, so equal numbers always have the same variant.
+impl From<BigInt> for RispExp {
+    fn from(i: BigInt) -> Self {
+        match i.to_i64() {
+            Some(i) => RispExp::Integer(i),
+            None => RispExp::BigInt(i),
+        }
+    }
+}
+
+/// Whole ratios become integers, like (/ 4 2).
+impl From<BigRational> for RispExp {
+    fn from(r: BigRational) -> Self {
+        if r.denom().is_one() {
+            r.to_integer().into()
+        } else {
+            RispExp::Ratio(Box::new(r))
+        }
+    }
+}
+
+impl RispExp {
+    /// Exact value of an integer or ratio.
+    pub(crate) fn to_ratio(&self) -> Option<BigRational> {
+        match self {
+            RispExp::Integer(i) => Some(BigRational::from_integer((*i).into())),
+            RispExp::BigInt(i) => Some(BigRational::from_integer(i.clone())),
+            RispExp::Ratio(r) => Some(r.as_ref().clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn to_float(&self) -> Option<f64> {
+        match self {
+            RispExp::Integer(i) => Some(*i as f64),
+            RispExp::BigInt(i) => i.to_f64(),
+            RispExp::Ratio(r) => r.to_f64(),
+            RispExp::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+}
+
+/// Numbers of any kind compare by value, exactly unless a float is involved. Strings and chars
+/// only compare with their own kind.
 impl PartialOrd for RispExp {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match (self, other) {
             (RispExp::Integer(a), RispExp::Integer(b)) => a.partial_cmp(b),
-            (RispExp::Integer(a), RispExp::Float(b)) => f64::from(*a).partial_cmp(b),
-            (RispExp::Float(a), RispExp::Integer(b)) => a.partial_cmp(&f64::from(*b)),
-            (RispExp::Float(a), RispExp::Float(b)) => a.partial_cmp(b),
-            _ => None,
+            (RispExp::Float(_), _) | (_, RispExp::Float(_)) => {
+                self.to_float()?.partial_cmp(&other.to_float()?)
+            }
+            (RispExp::String(a), RispExp::String(b)) => a.partial_cmp(b),
+            (RispExp::Char(a), RispExp::Char(b)) => a.partial_cmp(b),
+            _ => self.to_ratio()?.partial_cmp(&other.to_ratio()?),
         }
     }
 }
 
+/// RispExp is used as a map key, so equality has to be an equivalence. Only a NaN float breaks that,
+/// it is never equal to itself and so can be put in a map but never found.
+impl Eq for RispExp {}
+
+/// Hashes agree with the derived PartialEq: values of different variants are never equal, functions
+/// and atoms are only hashed by variant, and maps and sets combine their entries' hashes in an
+/// order independent way since they are equal in any order.
+impl Hash for RispExp {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            RispExp::Symbol(s) | RispExp::Keyword(s) | RispExp::String(s) => s.hash(state),
+            RispExp::Regex(r) => r.pattern().hash(state),
+            RispExp::Bool(b) => b.hash(state),
+            RispExp::Integer(i) => i.hash(state),
+            RispExp::BigInt(i) => i.hash(state),
+            RispExp::Ratio(r) => r.hash(state),
+            // -0.0 == 0.0 so they must hash the same.
+            RispExp::Float(f) => (if *f == 0.0 { 0.0 } else { *f }).to_bits().hash(state),
+            RispExp::Char(c) => c.hash(state),
+            RispExp::List(items) | RispExp::Vector(items) => items.hash(state),
+            RispExp::Map(map) => unordered_hash(map.iter()).hash(state),
+            RispExp::Set(set) => unordered_hash(set.iter()).hash(state),
+            RispExp::Empty
+            | RispExp::Nil
+            | RispExp::Func(_)
+            | RispExp::Lambda(_)
+            | RispExp::Macro(_)
+            | RispExp::Atom(_) => {}
+        }
+    }
+}
+
+fn join_items<'a>(items: impl Iterator<Item = &'a RispExp>) -> String {
+    items
+        .map(|x| format!("{}", x))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn unordered_hash(items: impl Iterator<Item = impl Hash>) -> u64 {
+    items
+        .map(|item| {
+            let mut hasher = DefaultHasher::new();
+            item.hash(&mut hasher);
+            hasher.finish()
+        })
+        .fold(0, u64::wrapping_add)
+}
+
 impl Display for RispExp {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -164,6 +511,7 @@ impl Display for RispExp {
             match self {
                 RispExp::Nil => "nil".to_owned(),
                 RispExp::Symbol(s) => format!("'{}", s),
+                RispExp::Keyword(k) => format!(":{}", k),
                 RispExp::Bool(b) =>
                     if *b {
                         "#t".to_owned()
@@ -171,35 +519,61 @@ impl Display for RispExp {
                         "#f".to_owned()
                     },
                 RispExp::Integer(i) => i.to_string(),
+                RispExp::BigInt(i) => i.to_string(),
+                RispExp::Ratio(r) => r.to_string(),
                 RispExp::Float(f) => f.to_string(),
                 RispExp::Char(c) => c.to_string(),
                 RispExp::String(s) => s.clone(),
-                RispExp::List(l) => {
-                    let lstr = l
-                        .iter()
-                        .map(|x| format!("{}", x))
+                RispExp::Regex(r) => format!("#\"{}\"", r.pattern()),
+                RispExp::List(l) => format!("({})", join_items(l.iter())),
+                RispExp::Vector(v) => format!("[{}]", join_items(v.iter())),
+                RispExp::Map(m) => format!(
+                    "{{{}}}",
+                    m.iter()
+                        .map(|(k, v)| format!("{} {}", k, v))
                         .collect::<Vec<_>>()
-                        .join(", ");
-                    format!("({})", lstr)
-                }
+                        .join(", ")
+                ),
+                RispExp::Set(s) => format!("#{{{}}}", join_items(s.iter())),
                 RispExp::Func(f) => format!("f@{}", f),
                 RispExp::Lambda(f) => format!("lambda@{}", f),
+                RispExp::Macro(f) => format!("macro@{}", f),
+                RispExp::Atom(a) => format!("atom@{}", a.value()),
                 RispExp::Empty => "".to_owned(),
             }
         )
     }
 }
 
-// TODO NOW change from Box to Ref and propogate
 #[derive(Clone, PartialEq)]
 pub enum RispFunction {
     Function {
-        params: Box<RispExp>,
-        body: Box<RispExp>,
+        /// One clause per arity, a call runs the first clause whose params accept its args.
+        clauses: Rc<[FnClause]>,
+        /// The env the lambda was evaluated in, None until the literal is evaluated.
+        env: Option<RispEnv>,
     },
     Builtin(RispBuiltinFunction),
 }
 
+#[derive(Clone, Debug, PartialEq)]
+pub struct FnClause {
+    pub params: Rc<RispExp>,
+    pub body: Rc<RispExp>,
+    /// The params parsed for binding args, None until the fn literal is evaluated.
+    pub(crate) spec: Option<Rc<ParamSpec>>,
+}
+
+impl FnClause {
+    pub(crate) fn new(params: RispExp, body: Vec<RispExp>) -> FnClause {
+        FnClause {
+            params: Rc::new(params),
+            body: Rc::new(implicit_do(body)),
+            spec: None,
+        }
+    }
+}
+
 #[derive(Clone, PartialEq)]
 pub enum RispBuiltinFunction {
     // Math
@@ -214,8 +588,7 @@ pub enum RispBuiltinFunction {
     Or,
     And,
 
-    // Comparison
-    // TODO make these cascade (n-ary) like in clojure
+    // Comparison, these check every adjacent pair of args like in clojure
     LT,
     LTE,
     GT,
@@ -224,27 +597,167 @@ pub enum RispBuiltinFunction {
 
     Def,
     If,
-    // Maybe add set!
+    Do,
+    Set,
+    Cond,
+    Case,
+    Match,
+
+    // Local bindings
+    Let,
+    LetStar,
+    Letrec,
+    Loop,
+    Recur,
+
+    // Macros
+    Defmacro,
+    Macroexpand,
+    Macroexpand1,
+
+    // Quoting
+    Quote,
+    Quasiquote,
+    Unquote,
+    UnquoteSplicing,
+
+    // Atoms
+    Atom,
+    Deref,
+    Swap,
+    Reset,
+
+    // Exceptions
+    Throw,
+    Try,
+
+    // Namespaces
+    Ns,
+    Require,
+
+    // Sequences
+    Cons,
+    First,
+    Rest,
+    Nth,
+    Count,
+    IsEmpty,
+    Map,
+    Filter,
+    Reduce,
+    Concat,
+    Reverse,
+    Range,
+
+    // Strings
+    Str,
+    Subs,
+    StrLen,
+    Upper,
+    Lower,
+    Trim,
+    Split,
+    Join,
+    StartsWith,
+    IndexOf,
+    Replace,
+
+    // Regexes
+    ReFind,
+    ReMatches,
+    ReSeq,
+    ReReplace,
+}
+
+impl RispBuiltinFunction {
+    /// The library functions, unlike the builtins recognised by [RispFunction::is_builtin] they
+    /// aren't reserved names but bindings in the root env, so they can be shadowed and redefined.
+    pub const LIBRARY: &'static [RispBuiltinFunction] = &[
+        RispBuiltinFunction::Cons,
+        RispBuiltinFunction::First,
+        RispBuiltinFunction::Rest,
+        RispBuiltinFunction::Nth,
+        RispBuiltinFunction::Count,
+        RispBuiltinFunction::IsEmpty,
+        RispBuiltinFunction::Map,
+        RispBuiltinFunction::Filter,
+        RispBuiltinFunction::Reduce,
+        RispBuiltinFunction::Concat,
+        RispBuiltinFunction::Reverse,
+        RispBuiltinFunction::Range,
+        RispBuiltinFunction::Str,
+        RispBuiltinFunction::Subs,
+        RispBuiltinFunction::StrLen,
+        RispBuiltinFunction::Upper,
+        RispBuiltinFunction::Lower,
+        RispBuiltinFunction::Trim,
+        RispBuiltinFunction::Split,
+        RispBuiltinFunction::Join,
+        RispBuiltinFunction::StartsWith,
+        RispBuiltinFunction::IndexOf,
+        RispBuiltinFunction::Replace,
+        RispBuiltinFunction::ReFind,
+        RispBuiltinFunction::ReMatches,
+        RispBuiltinFunction::ReSeq,
+        RispBuiltinFunction::ReReplace,
+    ];
 }
 
 impl RispFunction {
     pub fn is_builtin(str: &str) -> bool {
-        match str {
-            PLUS_SYM | MINUS_SYM | MULTIPLY_SYM | DIV_SYM | XOR_SYM | OR_SYM | AND_SYM
-            | NOT_SYM | DEF_SYM | LAMBDA_SYM => true,
-            _ => false,
-        }
+        matches!(
+            str,
+            PLUS_SYM
+                | MINUS_SYM
+                | MULTIPLY_SYM
+                | DIV_SYM
+                | XOR_SYM
+                | OR_SYM
+                | AND_SYM
+                | NOT_SYM
+                | DEF_SYM
+                | LAMBDA_SYM
+                | DO_SYM
+                | LET_SYM
+                | LET_STAR_SYM
+                | LETREC_SYM
+                | DEFMACRO_SYM
+                | MACROEXPAND_SYM
+                | MACROEXPAND_1_SYM
+                | QUOTE_SYM
+                | QUASIQUOTE_SYM
+                | UNQUOTE_SYM
+                | UNQUOTE_SPLICING_SYM
+                | SET_SYM
+                | ATOM_SYM
+                | DEREF_SYM
+                | SWAP_SYM
+                | RESET_SYM
+                | LOOP_SYM
+                | RECUR_SYM
+                | COND_SYM
+                | CASE_SYM
+                | MATCH_SYM
+                | THROW_SYM
+                | TRY_SYM
+                | NS_SYM
+                | REQUIRE_SYM
+        )
     }
 
-    fn to_string(&self) -> String {
+    fn name(&self) -> String {
         match self {
-            RispFunction::Function { params, body } => {
-                format!(
-                    "fn \n\targs: {:?} \n\tbody: {:?}",
-                    params.as_ref(),
-                    body.as_ref()
-                )
-            }
+            RispFunction::Function { clauses, .. } => clauses
+                .iter()
+                .map(|FnClause { params, body, .. }| {
+                    format!(
+                        "fn \n\targs: {:?} \n\tbody: {:?}",
+                        params.as_ref(),
+                        body.as_ref()
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join("\n"),
             RispFunction::Builtin(RispBuiltinFunction::Plus) => PLUS_SYM.to_owned(),
             RispFunction::Builtin(RispBuiltinFunction::Minus) => MINUS_SYM.to_owned(),
             RispFunction::Builtin(RispBuiltinFunction::Multiply) => MULTIPLY_SYM.to_owned(),
@@ -263,6 +776,71 @@ impl RispFunction {
 
             RispFunction::Builtin(RispBuiltinFunction::Def) => DEF_SYM.to_owned(),
             RispFunction::Builtin(RispBuiltinFunction::If) => IF_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Do) => DO_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Set) => SET_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Cond) => COND_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Case) => CASE_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Match) => MATCH_SYM.to_owned(),
+
+            RispFunction::Builtin(RispBuiltinFunction::Let) => LET_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::LetStar) => LET_STAR_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Letrec) => LETREC_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Loop) => LOOP_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Recur) => RECUR_SYM.to_owned(),
+
+            RispFunction::Builtin(RispBuiltinFunction::Defmacro) => DEFMACRO_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Macroexpand) => MACROEXPAND_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Macroexpand1) => {
+                MACROEXPAND_1_SYM.to_owned()
+            }
+
+            RispFunction::Builtin(RispBuiltinFunction::Quote) => QUOTE_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Quasiquote) => QUASIQUOTE_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Unquote) => UNQUOTE_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::UnquoteSplicing) => {
+                UNQUOTE_SPLICING_SYM.to_owned()
+            }
+
+            RispFunction::Builtin(RispBuiltinFunction::Atom) => ATOM_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Deref) => DEREF_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Swap) => SWAP_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Reset) => RESET_SYM.to_owned(),
+
+            RispFunction::Builtin(RispBuiltinFunction::Throw) => THROW_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Try) => TRY_SYM.to_owned(),
+
+            RispFunction::Builtin(RispBuiltinFunction::Ns) => NS_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Require) => REQUIRE_SYM.to_owned(),
+
+            RispFunction::Builtin(RispBuiltinFunction::Cons) => CONS_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::First) => FIRST_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Rest) => REST_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Nth) => NTH_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Count) => COUNT_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::IsEmpty) => IS_EMPTY_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Map) => MAP_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Filter) => FILTER_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Reduce) => REDUCE_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Concat) => CONCAT_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Reverse) => REVERSE_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Range) => RANGE_SYM.to_owned(),
+
+            RispFunction::Builtin(RispBuiltinFunction::Str) => STR_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Subs) => SUBS_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::StrLen) => STR_LEN_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Upper) => UPPER_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Lower) => LOWER_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Trim) => TRIM_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Split) => SPLIT_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Join) => JOIN_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::StartsWith) => STARTS_WITH_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::IndexOf) => INDEX_OF_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::Replace) => REPLACE_SYM.to_owned(),
+
+            RispFunction::Builtin(RispBuiltinFunction::ReFind) => RE_FIND_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::ReMatches) => RE_MATCHES_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::ReSeq) => RE_SEQ_SYM.to_owned(),
+            RispFunction::Builtin(RispBuiltinFunction::ReReplace) => RE_REPLACE_SYM.to_owned(),
         }
     }
 }
@@ -285,6 +863,31 @@ impl From<&str> for RispFunction {
             EQ_SYM => RispFunction::Builtin(RispBuiltinFunction::EQ),
             DEF_SYM => RispFunction::Builtin(RispBuiltinFunction::Def),
             IF_SYM => RispFunction::Builtin(RispBuiltinFunction::If),
+            DO_SYM => RispFunction::Builtin(RispBuiltinFunction::Do),
+            SET_SYM => RispFunction::Builtin(RispBuiltinFunction::Set),
+            COND_SYM => RispFunction::Builtin(RispBuiltinFunction::Cond),
+            CASE_SYM => RispFunction::Builtin(RispBuiltinFunction::Case),
+            MATCH_SYM => RispFunction::Builtin(RispBuiltinFunction::Match),
+            LET_SYM => RispFunction::Builtin(RispBuiltinFunction::Let),
+            LET_STAR_SYM => RispFunction::Builtin(RispBuiltinFunction::LetStar),
+            LETREC_SYM => RispFunction::Builtin(RispBuiltinFunction::Letrec),
+            LOOP_SYM => RispFunction::Builtin(RispBuiltinFunction::Loop),
+            RECUR_SYM => RispFunction::Builtin(RispBuiltinFunction::Recur),
+            DEFMACRO_SYM => RispFunction::Builtin(RispBuiltinFunction::Defmacro),
+            MACROEXPAND_SYM => RispFunction::Builtin(RispBuiltinFunction::Macroexpand),
+            MACROEXPAND_1_SYM => RispFunction::Builtin(RispBuiltinFunction::Macroexpand1),
+            QUOTE_SYM => RispFunction::Builtin(RispBuiltinFunction::Quote),
+            QUASIQUOTE_SYM => RispFunction::Builtin(RispBuiltinFunction::Quasiquote),
+            UNQUOTE_SYM => RispFunction::Builtin(RispBuiltinFunction::Unquote),
+            UNQUOTE_SPLICING_SYM => RispFunction::Builtin(RispBuiltinFunction::UnquoteSplicing),
+            ATOM_SYM => RispFunction::Builtin(RispBuiltinFunction::Atom),
+            DEREF_SYM => RispFunction::Builtin(RispBuiltinFunction::Deref),
+            SWAP_SYM => RispFunction::Builtin(RispBuiltinFunction::Swap),
+            RESET_SYM => RispFunction::Builtin(RispBuiltinFunction::Reset),
+            THROW_SYM => RispFunction::Builtin(RispBuiltinFunction::Throw),
+            TRY_SYM => RispFunction::Builtin(RispBuiltinFunction::Try),
+            NS_SYM => RispFunction::Builtin(RispBuiltinFunction::Ns),
+            REQUIRE_SYM => RispFunction::Builtin(RispBuiltinFunction::Require),
             _ => panic!("This is not a valid built in!"),
         }
     }
@@ -292,29 +895,33 @@ impl From<&str> for RispFunction {
 
 impl Debug for RispFunction {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&self.to_string())
+        f.write_str(&self.name())
     }
 }
 
 impl Display for RispFunction {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&self.to_string())
+        f.write_str(&self.name())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use pretty_assertions::{assert_eq, assert_ne};
+    use std::rc::Rc;
 
-    use crate::parser::{parse, RispFunction};
-    use crate::parser::{RispBuiltinFunction, RispExp};
+    use crate::error::{
+        RispError, EXPECTED_FN_DEF_FOR_FN, MAP_LITERAL_NEEDS_PAIRS, UNEXPECTED_CLOSING_PAREN,
+    };
+    use crate::parser::{parse, FnClause, RispFunction};
+    use crate::parser::{RispBuiltinFunction, RispExp, RispRegex};
     use crate::tokenizer::RispToken;
 
     #[test]
     fn empty_list() {
         assert_eq!(
             parse(&[RispToken::LParen, RispToken::RParen]).unwrap(),
-            RispExp::List(vec![])
+            RispExp::List(Rc::new([]))
         );
     }
 
@@ -329,11 +936,11 @@ mod tests {
                 RispToken::RParen
             ])
             .unwrap(),
-            RispExp::List(vec![
+            RispExp::List(Rc::new([
                 RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
                 RispExp::Integer(1),
                 RispExp::Integer(2)
-            ])
+            ]))
         );
         assert_eq!(
             parse(&[
@@ -344,11 +951,11 @@ mod tests {
                 RispToken::RParen
             ])
             .unwrap(),
-            RispExp::List(vec![
+            RispExp::List(Rc::new([
                 RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Minus)),
                 RispExp::Integer(1),
                 RispExp::Integer(2)
-            ])
+            ]))
         );
         assert_eq!(
             parse(&[
@@ -359,11 +966,11 @@ mod tests {
                 RispToken::RParen
             ])
             .unwrap(),
-            RispExp::List(vec![
+            RispExp::List(Rc::new([
                 RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Multiply)),
                 RispExp::Integer(1),
                 RispExp::Integer(2)
-            ])
+            ]))
         );
         assert_eq!(
             parse(&[
@@ -374,11 +981,11 @@ mod tests {
                 RispToken::RParen
             ])
             .unwrap(),
-            RispExp::List(vec![
+            RispExp::List(Rc::new([
                 RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Divide)),
                 RispExp::Integer(1),
                 RispExp::Integer(2)
-            ])
+            ]))
         );
         assert_eq!(
             parse(&[
@@ -389,11 +996,11 @@ mod tests {
                 RispToken::RParen
             ])
             .unwrap(),
-            RispExp::List(vec![
+            RispExp::List(Rc::new([
                 RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Not)),
                 RispExp::Integer(1),
                 RispExp::Integer(2)
-            ])
+            ]))
         );
         assert_eq!(
             parse(&[
@@ -404,11 +1011,11 @@ mod tests {
                 RispToken::RParen
             ])
             .unwrap(),
-            RispExp::List(vec![
+            RispExp::List(Rc::new([
                 RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Xor)),
                 RispExp::Integer(1),
                 RispExp::Integer(2)
-            ])
+            ]))
         );
         assert_eq!(
             parse(&[
@@ -419,11 +1026,11 @@ mod tests {
                 RispToken::RParen
             ])
             .unwrap(),
-            RispExp::List(vec![
+            RispExp::List(Rc::new([
                 RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Or)),
                 RispExp::Integer(1),
                 RispExp::Integer(2)
-            ])
+            ]))
         );
         assert_eq!(
             parse(&[
@@ -434,11 +1041,11 @@ mod tests {
                 RispToken::RParen
             ])
             .unwrap(),
-            RispExp::List(vec![
+            RispExp::List(Rc::new([
                 RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::And)),
                 RispExp::Integer(1),
                 RispExp::Integer(2)
-            ])
+            ]))
         );
     }
 
@@ -453,11 +1060,11 @@ mod tests {
                 RispToken::RParen
             ])
             .unwrap(),
-            RispExp::List(vec![
+            RispExp::List(Rc::new([
                 RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Or)),
                 RispExp::Integer(1),
                 RispExp::Integer(2)
-            ])
+            ]))
         );
     }
 
@@ -472,11 +1079,11 @@ mod tests {
                 RispToken::RParen
             ])
             .unwrap(),
-            RispExp::List(vec![
+            RispExp::List(Rc::new([
                 RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Def)),
                 RispExp::Symbol("lukesfather".to_owned()),
                 RispExp::String("darthvader".to_owned())
-            ])
+            ]))
         );
     }
 
@@ -492,12 +1099,12 @@ mod tests {
                 RispToken::RParen
             ])
             .unwrap(),
-            RispExp::List(vec![
+            RispExp::List(Rc::new([
                 RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::If)),
                 RispExp::Bool(true),
                 RispExp::String("true".to_owned()),
                 RispExp::String("false".to_owned())
-            ])
+            ]))
         );
     }
 
@@ -518,15 +1125,228 @@ mod tests {
                 RispToken::RParen,
             ])
             .unwrap(),
-            RispExp::List(vec![RispExp::Lambda(RispFunction::Function {
-                params: Box::new(RispExp::List(vec![RispExp::Symbol("x".to_string())])),
-                body: Box::new(RispExp::List(vec![
-                    RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
-                    RispExp::Symbol("x".to_string()),
-                    RispExp::Integer(1),
+            RispExp::List(Rc::new([RispExp::Lambda(RispFunction::Function {
+                clauses: Rc::new([FnClause {
+                    params: Rc::new(RispExp::List(Rc::new([RispExp::Symbol("x".to_string())]))),
+                    body: Rc::new(RispExp::List(Rc::new([
+                        RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
+                        RispExp::Symbol("x".to_string()),
+                        RispExp::Integer(1),
+                    ]))),
+                    spec: None,
+                }]),
+                env: None,
+            })]))
+        );
+    }
+
+    #[test]
+    fn fn_bodies_are_wrapped_in_do() {
+        assert_eq!(
+            parse(&[
+                RispToken::LParen,
+                RispToken::Fn,
+                RispToken::LParen,
+                RispToken::Symbol("x".to_owned()),
+                RispToken::RParen,
+                RispToken::LParen,
+                RispToken::Symbol("print".to_owned()),
+                RispToken::Symbol("x".to_owned()),
+                RispToken::RParen,
+                RispToken::Symbol("x".to_owned()),
+                RispToken::RParen,
+            ])
+            .unwrap(),
+            RispExp::List(Rc::new([RispExp::Lambda(RispFunction::Function {
+                clauses: Rc::new([FnClause {
+                    params: Rc::new(RispExp::List(Rc::new([RispExp::Symbol("x".to_string())]))),
+                    body: Rc::new(RispExp::List(Rc::new([
+                        RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Do)),
+                        RispExp::List(Rc::new([
+                            RispExp::Symbol("print".to_string()),
+                            RispExp::Symbol("x".to_string()),
+                        ])),
+                        RispExp::Symbol("x".to_string()),
+                    ]))),
+                    spec: None,
+                }]),
+                env: None,
+            })]))
+        );
+    }
+
+    #[test]
+    fn multi_arity_fn_has_a_clause_per_arity() {
+        assert_eq!(
+            parse(&[
+                RispToken::LParen,
+                RispToken::Fn,
+                RispToken::LParen,
+                RispToken::LBracket,
+                RispToken::RBracket,
+                RispToken::Integer(0),
+                RispToken::RParen,
+                RispToken::LParen,
+                RispToken::LBracket,
+                RispToken::Symbol("x".to_owned()),
+                RispToken::RBracket,
+                RispToken::Symbol("x".to_owned()),
+                RispToken::RParen,
+                RispToken::RParen,
+            ])
+            .unwrap(),
+            RispExp::List(Rc::new([RispExp::Lambda(RispFunction::Function {
+                clauses: Rc::new([
+                    FnClause {
+                        params: Rc::new(RispExp::Vector(Rc::new([]))),
+                        body: Rc::new(RispExp::Integer(0)),
+                        spec: None,
+                    },
+                    FnClause {
+                        params: Rc::new(RispExp::Vector(Rc::new([RispExp::Symbol(
+                            "x".to_string()
+                        )]))),
+                        body: Rc::new(RispExp::Symbol("x".to_string())),
+                        spec: None,
+                    },
+                ]),
+                env: None,
+            })]))
+        );
+    }
+
+    #[test]
+    fn fn_without_body_is_an_error() {
+        assert_eq!(
+            parse(&[
+                RispToken::LParen,
+                RispToken::Fn,
+                RispToken::LParen,
+                RispToken::RParen,
+                RispToken::RParen,
+            ])
+            .unwrap_err(),
+            RispError::UnexpectedToken(RispToken::RParen, EXPECTED_FN_DEF_FOR_FN.to_owned())
+        );
+    }
+
+    #[test]
+    fn reader_macros_are_lowered() {
+        assert_eq!(
+            parse(&[
+                RispToken::Quasiquote,
+                RispToken::LParen,
+                RispToken::Symbol("a".to_owned()),
+                RispToken::Unquote,
+                RispToken::Symbol("b".to_owned()),
+                RispToken::UnquoteSplicing,
+                RispToken::Quote,
+                RispToken::LParen,
+                RispToken::RParen,
+                RispToken::RParen,
+            ])
+            .unwrap(),
+            RispExp::List(Rc::new([
+                RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Quasiquote)),
+                RispExp::List(Rc::new([
+                    RispExp::Symbol("a".to_owned()),
+                    RispExp::List(Rc::new([
+                        RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Unquote)),
+                        RispExp::Symbol("b".to_owned()),
+                    ])),
+                    RispExp::List(Rc::new([
+                        RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::UnquoteSplicing)),
+                        RispExp::List(Rc::new([
+                            RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Quote)),
+                            RispExp::List(Rc::new([])),
+                        ])),
+                    ])),
                 ])),
-            })])
+            ]))
+        );
+    }
+
+    #[test]
+    fn dangling_reader_macro_is_an_error() {
+        assert_eq!(
+            parse(&[RispToken::Quote]).unwrap_err(),
+            RispError::UnexpectedEndOfInput
+        );
+    }
+
+    #[test]
+    fn collection_literals() {
+        assert_eq!(
+            parse(&[
+                RispToken::LBracket,
+                RispToken::Integer(1),
+                RispToken::LBrace,
+                RispToken::Keyword("a".to_owned()),
+                RispToken::SetLBrace,
+                RispToken::Integer(2),
+                RispToken::Integer(2),
+                RispToken::RBrace,
+                RispToken::RBrace,
+                RispToken::RBracket,
+            ])
+            .unwrap(),
+            RispExp::Vector(Rc::new([
+                RispExp::Integer(1),
+                RispExp::Map(Box::new(
+                    [(
+                        RispExp::Keyword("a".to_owned()),
+                        RispExp::Set(Box::new([RispExp::Integer(2)].into_iter().collect())),
+                    )]
+                    .into_iter()
+                    .collect()
+                )),
+            ]))
+        );
+    }
+
+    #[test]
+    fn malformed_collection_literals_are_errors() {
+        assert_eq!(
+            parse(&[
+                RispToken::LBrace,
+                RispToken::Keyword("a".to_owned()),
+                RispToken::RBrace,
+            ]),
+            Err(RispError::UnexpectedToken(
+                RispToken::RBrace,
+                MAP_LITERAL_NEEDS_PAIRS.to_owned()
+            ))
         );
+        assert_eq!(
+            parse(&[
+                RispToken::LBracket,
+                RispToken::Integer(1),
+                RispToken::RParen
+            ]),
+            Err(RispError::UnexpectedToken(
+                RispToken::RParen,
+                UNEXPECTED_CLOSING_PAREN.to_owned()
+            ))
+        );
+        assert_eq!(
+            parse(&[RispToken::SetLBrace, RispToken::Integer(1)]),
+            Err(RispError::UnterminatedList)
+        );
+    }
+
+    #[test]
+    fn regex_literals_are_compiled() {
+        assert_eq!(
+            parse(&[RispToken::RegexLiteral("a+".to_owned())]).unwrap(),
+            RispExp::Regex(RispRegex::new("a+").unwrap())
+        );
+        match parse(&[RispToken::RegexLiteral("a(".to_owned())]) {
+            Err(RispError::InvalidRegex(pattern, message)) => {
+                assert_eq!(pattern, "a(");
+                assert!(message.contains("unclosed group"), "{}", message);
+            }
+            other => panic!("Expected an invalid regex error, got {:?}", other),
+        }
     }
 
     // TODO TEST non lists (ints, floats, bools, symbols etc)
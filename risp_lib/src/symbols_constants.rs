@@ -1,24 +1,105 @@
-pub const LPAREN_SYM: &'static str = "(";
-pub const RPAREN_SYM: &'static str = ")";
-
-pub const PLUS_SYM: &'static str = "+";
-pub const MINUS_SYM: &'static str = "-";
-pub const MULTIPLY_SYM: &'static str = "*";
-pub const DIV_SYM: &'static str = "/";
-
-pub const NOT_SYM: &'static str = "not";
-pub const XOR_SYM: &'static str = "xor";
-pub const OR_SYM: &'static str = "or";
-pub const AND_SYM: &'static str = "and";
-
-pub const EQ_SYM: &'static str = "=";
-pub const LT_SYM: &'static str = "<";
-pub const LTE_SYM: &'static str = "<=";
-pub const GT_SYM: &'static str = ">";
-pub const GTE_SYM: &'static str = ">=";
-
-pub const DEF_SYM: &'static str = "def";
-pub const IF_SYM: &'static str = "if";
-pub const LAMBDA_SYM: &'static str = "fn";
-
-pub const NIL_SYM: &'static str = "nil";
+pub const LPAREN_SYM: &str = "(";
+pub const RPAREN_SYM: &str = ")";
+pub const LBRACKET_SYM: &str = "[";
+pub const RBRACKET_SYM: &str = "]";
+pub const LBRACE_SYM: &str = "{";
+pub const RBRACE_SYM: &str = "}";
+pub const SET_LBRACE_SYM: &str = "#{";
+
+pub const PLUS_SYM: &str = "+";
+pub const MINUS_SYM: &str = "-";
+pub const MULTIPLY_SYM: &str = "*";
+pub const DIV_SYM: &str = "/";
+
+pub const NOT_SYM: &str = "not";
+pub const XOR_SYM: &str = "xor";
+pub const OR_SYM: &str = "or";
+pub const AND_SYM: &str = "and";
+
+pub const EQ_SYM: &str = "=";
+pub const LT_SYM: &str = "<";
+pub const LTE_SYM: &str = "<=";
+pub const GT_SYM: &str = ">";
+pub const GTE_SYM: &str = ">=";
+
+pub const DEF_SYM: &str = "def";
+pub const IF_SYM: &str = "if";
+pub const LAMBDA_SYM: &str = "fn";
+pub const DO_SYM: &str = "do";
+pub const SET_SYM: &str = "set!";
+pub const COND_SYM: &str = "cond";
+pub const CASE_SYM: &str = "case";
+pub const MATCH_SYM: &str = "match";
+
+pub const REST_PARAM_SYM: &str = "&";
+pub const OPTIONAL_PARAM_SYM: &str = "&opt";
+pub const KEY_PARAM_SYM: &str = "&key";
+pub const WILDCARD_SYM: &str = "_";
+
+pub const LET_SYM: &str = "let";
+pub const LET_STAR_SYM: &str = "let*";
+pub const LETREC_SYM: &str = "letrec";
+pub const LOOP_SYM: &str = "loop";
+pub const RECUR_SYM: &str = "recur";
+
+pub const DEFMACRO_SYM: &str = "defmacro";
+pub const MACROEXPAND_SYM: &str = "macroexpand";
+pub const MACROEXPAND_1_SYM: &str = "macroexpand-1";
+
+pub const QUOTE_SYM: &str = "quote";
+pub const QUASIQUOTE_SYM: &str = "quasiquote";
+pub const UNQUOTE_SYM: &str = "unquote";
+pub const UNQUOTE_SPLICING_SYM: &str = "unquote-splicing";
+
+pub const ATOM_SYM: &str = "atom";
+pub const DEREF_SYM: &str = "deref";
+pub const SWAP_SYM: &str = "swap!";
+pub const RESET_SYM: &str = "reset!";
+
+pub const THROW_SYM: &str = "throw";
+pub const TRY_SYM: &str = "try";
+pub const CATCH_SYM: &str = "catch";
+pub const FINALLY_SYM: &str = "finally";
+
+pub const NS_SYM: &str = "ns";
+pub const REQUIRE_SYM: &str = "require";
+pub const AS_OPTION_KEYWORD: &str = "as";
+pub const REFER_OPTION_KEYWORD: &str = "refer";
+pub const REFER_ALL_KEYWORD: &str = "all";
+
+pub const CONS_SYM: &str = "cons";
+pub const FIRST_SYM: &str = "first";
+pub const REST_SYM: &str = "rest";
+pub const NTH_SYM: &str = "nth";
+pub const COUNT_SYM: &str = "count";
+pub const IS_EMPTY_SYM: &str = "empty?";
+pub const MAP_SYM: &str = "map";
+pub const FILTER_SYM: &str = "filter";
+pub const REDUCE_SYM: &str = "reduce";
+pub const CONCAT_SYM: &str = "concat";
+pub const REVERSE_SYM: &str = "reverse";
+pub const RANGE_SYM: &str = "range";
+
+pub const STR_SYM: &str = "str";
+pub const SUBS_SYM: &str = "subs";
+pub const STR_LEN_SYM: &str = "str-len";
+pub const UPPER_SYM: &str = "upper";
+pub const LOWER_SYM: &str = "lower";
+pub const TRIM_SYM: &str = "trim";
+pub const SPLIT_SYM: &str = "split";
+pub const JOIN_SYM: &str = "join";
+pub const STARTS_WITH_SYM: &str = "starts-with?";
+pub const INDEX_OF_SYM: &str = "index-of";
+pub const REPLACE_SYM: &str = "replace";
+
+pub const RE_FIND_SYM: &str = "re-find";
+pub const RE_MATCHES_SYM: &str = "re-matches";
+pub const RE_SEQ_SYM: &str = "re-seq";
+pub const RE_REPLACE_SYM: &str = "re-replace";
+
+pub const QUOTE_READER_SYM: &str = "'";
+pub const QUASIQUOTE_READER_SYM: &str = "`";
+pub const UNQUOTE_READER_SYM: &str = "~";
+pub const UNQUOTE_SPLICING_READER_SYM: &str = "~@";
+
+pub const NIL_SYM: &str = "nil";
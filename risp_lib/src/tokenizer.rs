@@ -1,114 +1,335 @@
 use crate::error::{RispError, RispResult};
 use crate::symbols_constants::{
-    DEF_SYM, EQ_SYM, GTE_SYM, GT_SYM, IF_SYM, LAMBDA_SYM, LPAREN_SYM, LTE_SYM, LT_SYM, NIL_SYM,
-    RPAREN_SYM,
+    DEF_SYM, EQ_SYM, GTE_SYM, GT_SYM, IF_SYM, LAMBDA_SYM, LBRACE_SYM, LBRACKET_SYM, LPAREN_SYM,
+    LTE_SYM, LT_SYM, NIL_SYM, QUASIQUOTE_READER_SYM, QUOTE_READER_SYM, RBRACE_SYM, RBRACKET_SYM,
+    RPAREN_SYM, SET_LBRACE_SYM, UNQUOTE_READER_SYM, UNQUOTE_SPLICING_READER_SYM,
 };
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::Zero;
 use regex::Regex;
+use std::fmt::{Display, Formatter};
 use std::str::FromStr;
 
 pub fn tokenize(line: &str) -> RispResult<Vec<RispToken>> {
-    Tokenizer::new().tokenize(line)
+    Ok(tokenize_with_positions(line)?.0)
+}
+
+/// Like [tokenize], along with the position every token starts at. An error is wrapped in
+/// [RispError::InToken] with the position of the token it is in.
+pub fn tokenize_with_positions(source: &str) -> RispResult<(Vec<RispToken>, Vec<Position>)> {
+    Ok(Tokenizer::new().tokenize(source)?.into_iter().unzip())
+}
+
+/// A place in the source, lines and columns count from 1 and a column counts chars, not bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    pub const START: Position = Position { line: 1, column: 1 };
+
+    /// The position of the char after c, when c is at this one.
+    fn after(self, c: char) -> Position {
+        match c {
+            '\n' => Position {
+                line: self.line + 1,
+                column: 1,
+            },
+            _ => Position {
+                column: self.column + 1,
+                ..self
+            },
+        }
+    }
+
+    /// The position of the char before this one on the same line.
+    fn before(self) -> Position {
+        Position {
+            column: self.column - 1,
+            ..self
+        }
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// The chars of the source being scanned, keeping track of the position of the next one.
+struct Chars<'a> {
+    chars: std::str::Chars<'a>,
+    position: Position,
+}
+
+impl<'a> Chars<'a> {
+    fn new(source: &'a str) -> Chars<'a> {
+        Chars {
+            chars: source.chars(),
+            position: Position::START,
+        }
+    }
+
+    fn next_if(&mut self, test: impl FnOnce(&char) -> bool) -> Option<char> {
+        self.chars.clone().next().filter(test)?;
+        self.next()
+    }
+
+    fn next_if_eq(&mut self, expected: &char) -> Option<char> {
+        self.next_if(|c| c == expected)
+    }
+}
+
+impl Iterator for Chars<'_> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        self.position = self.position.after(c);
+        Some(c)
+    }
 }
 
 struct Tokenizer {
     char_matcher: Regex,
     bool_matcher: Regex,
     int_matcher: Regex,
+    ratio_matcher: Regex,
     float_matcher: Regex,
     symbol_matcher: Regex,
-    string_literal_matcher: Regex,
+    keyword_matcher: Regex,
     comparison_op_matcher: Regex,
 }
 
-// TODO NOW quote tokens and reader macros for ' (quote), !=
+// TODO NOW !=
 impl Tokenizer {
     fn new() -> Tokenizer {
         Tokenizer {
             char_matcher: Regex::new(r#"\\[\x00-\xFF]"#).unwrap(),
             bool_matcher: Regex::new(r#"^(true|false)$"#).unwrap(),
             int_matcher: Regex::new(r#"^[-+]?[0-9][0-9_]*$"#).unwrap(),
-            float_matcher: Regex::new(r#"^[-+]?[0-9]*([.][0-9]+|f|[.][0-9]+f)$"#).unwrap(),
-            symbol_matcher: Regex::new(r#"^([A-Za-z_]+[A-Za-z0-9_]*|\+|-|\*|/)$"#).unwrap(),
-            string_literal_matcher: Regex::new(r#"^".*"$"#).unwrap(),
-            comparison_op_matcher: Regex::new(r#"(<|>|<=|>=|=)"#).unwrap(),
+            ratio_matcher: Regex::new(r#"^[-+]?[0-9][0-9_]*/[0-9][0-9_]*$"#).unwrap(),
+            float_matcher: Regex::new(r#"^[-+]?([0-9]+f|[0-9]*[.][0-9]+f?)$"#).unwrap(),
+            symbol_matcher: Regex::new(r#"^([A-Za-z_*!?<>=&\-][A-Za-z0-9_*!?<>=&:./\-]*|\+|/)$"#)
+                .unwrap(),
+            keyword_matcher: Regex::new(r#"^:[A-Za-z0-9_*!?<>=&:./\-]+$"#).unwrap(),
+            comparison_op_matcher: Regex::new(r#"^(<|>|<=|>=|=)$"#).unwrap(),
         }
     }
 
-    fn tokenize(&self, line: &str) -> RispResult<Vec<RispToken>> {
-        Self::combine_quote_pairs(
-            line.replace("(", " ( ")
-                .replace(")", " ) ")
-                .replace("\"", " \" ")
-                .split_whitespace()
-                .map(|x| x.to_owned())
-                .collect::<Vec<String>>(),
-        )?
-        .iter()
-        .map(|s| self.tokenize_element(&s))
-        .collect()
-    }
-
-    // TODO NOW allow escape chars for quotes
-    fn combine_quote_pairs(inputs: Vec<String>) -> RispResult<Vec<String>> {
-        let mut result: Vec<String> = Vec::new();
-        let mut quoted_string_builder: String = String::new();
-        let mut quoting = false;
-        for input in inputs.iter() {
-            match (quoting, input.as_str()) {
-                (false, s) => {
-                    if s == "\"" {
-                        quoting = true;
-                        quoted_string_builder = s.to_string();
-                    } else {
-                        result.push(s.to_owned());
+    /// Scans line a char at a time. Brackets and whitespace end the element being read, and string
+    /// and regex literals are read whole so their content is kept exactly as written.
+    fn tokenize(&self, line: &str) -> RispResult<Vec<(RispToken, Position)>> {
+        let mut tokens = vec![];
+        let mut element = String::new();
+        let mut element_start = Position::START;
+        let mut chars = Chars::new(line);
+        loop {
+            let position = chars.position;
+            let Some(c) = chars.next() else { break };
+            match c {
+                '"' => {
+                    // A # stuck to the opening quote makes it a regex literal.
+                    let is_regex = element.ends_with('#');
+                    if is_regex {
+                        element.pop();
                     }
+                    self.end_element(&mut element, element_start, &mut tokens)?;
+                    tokens.push(if is_regex {
+                        let position = position.before();
+                        let pattern =
+                            Self::read_regex_literal(&mut chars).map_err(in_token(position))?;
+                        (RispToken::RegexLiteral(pattern), position)
+                    } else {
+                        let s =
+                            Self::read_string_literal(&mut chars).map_err(in_token(position))?;
+                        (RispToken::StringLiteral(s), position)
+                    });
                 }
-                (true, s) => {
-                    if s == "\"" {
-                        let start = quoted_string_builder.len() - 1;
-                        quoted_string_builder.replace_range(start..start + 1, "\"");
-                        quoting = false;
-                        result.push(quoted_string_builder.clone())
+                '(' | ')' | '[' | ']' | '{' | '}' => {
+                    // Likewise a # stuck to a brace opens a set literal.
+                    let is_set = c == '{' && element.ends_with('#');
+                    if is_set {
+                        element.pop();
+                    }
+                    self.end_element(&mut element, element_start, &mut tokens)?;
+                    let mut buf = [0; 4];
+                    tokens.push(if is_set {
+                        (self.tokenize_element(SET_LBRACE_SYM)?, position.before())
                     } else {
-                        quoted_string_builder.push_str(s);
-                        quoted_string_builder.push(' ');
+                        (self.tokenize_element(c.encode_utf8(&mut buf))?, position)
+                    });
+                }
+                c if c.is_whitespace() => {
+                    self.end_element(&mut element, element_start, &mut tokens)?
+                }
+                c => {
+                    if element.is_empty() {
+                        element_start = position;
                     }
+                    element.push(c)
+                }
+            }
+        }
+        self.end_element(&mut element, element_start, &mut tokens)?;
+        Ok(tokens)
+    }
+
+    /// Tokenizes the element read so far, if there is one, and clears it for the next.
+    fn end_element(
+        &self,
+        element: &mut String,
+        start: Position,
+        tokens: &mut Vec<(RispToken, Position)>,
+    ) -> RispResult<()> {
+        if !element.is_empty() {
+            let mut position = start;
+            for part in Self::split_reader_macros(element) {
+                let token = self.tokenize_element(part).map_err(in_token(position))?;
+                tokens.push((token, position));
+                // An element is all on one line, the next part starts right after this one.
+                position.column += part.chars().count();
+            }
+            element.clear();
+        }
+        Ok(())
+    }
+
+    /// Reader macros are written stuck to the form they apply to ('x, ~@xs), split them off into
+    /// their own elements. Longer macros come first so ~@ isn't read as ~ followed by @.
+    fn split_reader_macros(elem: &str) -> Vec<&str> {
+        const READER_MACROS: [&str; 4] = [
+            UNQUOTE_SPLICING_READER_SYM,
+            UNQUOTE_READER_SYM,
+            QUASIQUOTE_READER_SYM,
+            QUOTE_READER_SYM,
+        ];
+
+        let mut parts = vec![];
+        let mut rest = elem;
+        while let Some(m) = READER_MACROS.iter().find(|m| rest.starts_with(*m)) {
+            parts.push(*m);
+            rest = &rest[m.len()..];
+        }
+        // Nothing is left when the macro was stuck to a bracket or string, as in ~@(f x).
+        if !rest.is_empty() {
+            parts.push(rest);
+        }
+        parts
+    }
+
+    /// Reads a string literal up to its closing quote, which has already been opened.
+    fn read_string_literal(chars: &mut Chars) -> RispResult<String> {
+        let mut s = String::new();
+        loop {
+            match chars.next().ok_or(RispError::UnterminatedString)? {
+                '"' => return Ok(s),
+                '\\' => s.push(Self::read_escape(chars)?),
+                c => s.push(c),
+            }
+        }
+    }
+
+    /// Reads what follows a backslash in a string literal, one of \n \t \" \\ or \u{hex}.
+    fn read_escape(chars: &mut Chars) -> RispResult<char> {
+        match chars.next().ok_or(RispError::UnterminatedString)? {
+            'n' => Ok('\n'),
+            't' => Ok('\t'),
+            '"' => Ok('"'),
+            '\\' => Ok('\\'),
+            'u' => {
+                let mut hex = String::new();
+                let braced = chars.next_if_eq(&'{').is_some();
+                while let Some(c) = chars.next_if(char::is_ascii_hexdigit) {
+                    hex.push(c);
+                }
+                let code = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32);
+                match (braced, code, chars.next_if_eq(&'}')) {
+                    (true, Some(c), Some(_)) => Ok(c),
+                    _ => Err(RispError::InvalidEscape(format!(
+                        "\\u{}{}",
+                        if braced { "{" } else { "" },
+                        hex
+                    ))),
                 }
             }
+            other => Err(RispError::InvalidEscape(format!("\\{}", other))),
         }
+    }
 
-        Ok(result)
+    /// Reads a regex literal up to its closing quote. Escapes are left for the regex to interpret,
+    /// apart from \" which is how a pattern holds a quote.
+    fn read_regex_literal(chars: &mut Chars) -> RispResult<String> {
+        let mut pattern = String::new();
+        loop {
+            match chars.next().ok_or(RispError::UnterminatedString)? {
+                '"' => return Ok(pattern),
+                '\\' => match chars.next().ok_or(RispError::UnterminatedString)? {
+                    '"' => pattern.push('"'),
+                    c => {
+                        pattern.push('\\');
+                        pattern.push(c);
+                    }
+                },
+                c => pattern.push(c),
+            }
+        }
     }
 
     fn tokenize_element(&self, elem: &str) -> RispResult<RispToken> {
         match elem {
             LPAREN_SYM => Ok(RispToken::LParen),
             RPAREN_SYM => Ok(RispToken::RParen),
+            LBRACKET_SYM => Ok(RispToken::LBracket),
+            RBRACKET_SYM => Ok(RispToken::RBracket),
+            LBRACE_SYM => Ok(RispToken::LBrace),
+            RBRACE_SYM => Ok(RispToken::RBrace),
+            SET_LBRACE_SYM => Ok(RispToken::SetLBrace),
             NIL_SYM => Ok(RispToken::Nil),
             DEF_SYM => Ok(RispToken::Def),
             IF_SYM => Ok(RispToken::If),
             LAMBDA_SYM => Ok(RispToken::Fn),
 
+            QUOTE_READER_SYM => Ok(RispToken::Quote),
+            QUASIQUOTE_READER_SYM => Ok(RispToken::Quasiquote),
+            UNQUOTE_READER_SYM => Ok(RispToken::Unquote),
+            UNQUOTE_SPLICING_READER_SYM => Ok(RispToken::UnquoteSplicing),
+
             c if self.char_matcher.is_match(c) => Ok(RispToken::Char(c.chars().nth(0).unwrap())),
             b if self.bool_matcher.is_match(b) => Ok(RispToken::Bool(bool::from_str(b)?)),
             int if self.int_matcher.is_match(int) => {
-                Ok(RispToken::Integer(i32::from_str(&int.replace("_", ""))?))
+                let int = int.replace("_", "");
+                match i64::from_str(&int) {
+                    Ok(i) => Ok(RispToken::Integer(i)),
+                    Err(_) => Ok(RispToken::BigInt(parse_bigint(&int))),
+                }
+            }
+            ratio if self.ratio_matcher.is_match(ratio) => {
+                let ratio = ratio.replace("_", "");
+                let (numer, denom) = ratio.split_once('/').unwrap();
+                let denom = parse_bigint(denom);
+                if denom.is_zero() {
+                    return Err(RispError::DivisionByZero);
+                }
+                Ok(RispToken::Ratio(BigRational::new(
+                    parse_bigint(numer),
+                    denom,
+                )))
             }
             float if self.float_matcher.is_match(float) => Ok(RispToken::Float(
-                f64::from_str(&float.replace("f", ""))
-                    .expect(&format!("Unable to parse {} as f64", float)),
+                f64::from_str(&float.replace('f', ""))
+                    .unwrap_or_else(|_| panic!("Unable to parse {} as f64", float)),
             )),
 
-            sym if self.symbol_matcher.is_match(sym) => Ok(RispToken::Symbol(sym.to_owned())),
-            string_literal if self.string_literal_matcher.is_match(string_literal) =>
-            // Cut out the " chars
-            {
-                Ok(RispToken::StringLiteral(
-                    string_literal[1..string_literal.len() - 1].to_owned(),
-                ))
-            }
-
+            // Comparisons are valid symbols too, so they have to be picked out first.
             o if self.comparison_op_matcher.is_match(o) => Ok(Self::tokenize_operator(o)),
+            sym if self.symbol_matcher.is_match(sym) => Ok(RispToken::Symbol(sym.to_owned())),
+            // Cut out the : prefix
+            kw if self.keyword_matcher.is_match(kw) => Ok(RispToken::Keyword(kw[1..].to_owned())),
 
             other => Err(RispError::UnrecognizedToken(other.to_string())),
         }
@@ -126,18 +347,37 @@ impl Tokenizer {
     }
 }
 
+fn in_token(position: Position) -> impl FnOnce(RispError) -> RispError {
+    move |e| RispError::InToken(position, Box::new(e))
+}
+
+/// The matchers only let digits through, so this can't fail.
+fn parse_bigint(int: &str) -> BigInt {
+    BigInt::from_str(int).unwrap_or_else(|_| panic!("Unable to parse {} as an integer", int))
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum RispToken {
     Nil,
     LParen,
     RParen,
+    LBracket,
+    RBracket,
+    LBrace,
+    RBrace,
+    // #{, the opening of a set literal.
+    SetLBrace,
 
     Symbol(String),
+    Keyword(String),
     StringLiteral(String),
+    RegexLiteral(String),
 
     Bool(bool),
     Float(f64),
-    Integer(i32),
+    Integer(i64),
+    BigInt(BigInt),
+    Ratio(BigRational),
     Char(char),
 
     Comparison(ComparisonOp),
@@ -146,6 +386,12 @@ pub enum RispToken {
     If,
 
     Fn,
+
+    // Reader macros, these wrap the form that follows them.
+    Quote,
+    Quasiquote,
+    Unquote,
+    UnquoteSplicing,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -159,10 +405,12 @@ pub enum ComparisonOp {
 
 #[cfg(test)]
 mod tests {
+    use num_bigint::BigInt;
+    use num_rational::BigRational;
     use pretty_assertions::assert_eq;
 
     use crate::error::RispError;
-    use crate::tokenizer::{tokenize, ComparisonOp, RispToken};
+    use crate::tokenizer::{tokenize, tokenize_with_positions, ComparisonOp, Position, RispToken};
 
     #[test]
     fn recognizes_operators_as_symbols() {
@@ -179,6 +427,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn recognizes_punctuated_symbols() {
+        for sym in [
+            "macroexpand-1",
+            "let*",
+            "set!",
+            "empty?",
+            "->",
+            "<foo>",
+            "&",
+            "&opt",
+            "str/join",
+            "text.str",
+        ] {
+            assert_eq!(
+                tokenize(sym).unwrap(),
+                vec![RispToken::Symbol(sym.to_string())]
+            );
+        }
+    }
+
+    #[test]
+    fn recognizes_keywords() {
+        for (kw, name) in [
+            (":as", "as"),
+            (":a-b?", "a-b?"),
+            (":my.ns/key", "my.ns/key"),
+            (":1", "1"),
+        ] {
+            assert_eq!(
+                tokenize(kw).unwrap(),
+                vec![RispToken::Keyword(name.to_string())]
+            );
+        }
+        assert!(matches!(
+            tokenize(":"),
+            Err(RispError::InToken(Position::START, e)) if matches!(*e, RispError::UnrecognizedToken(_))
+        ));
+    }
+
     #[test]
     fn recognizes_strings_as_symbols() {
         assert_eq!(
@@ -214,27 +502,103 @@ mod tests {
     }
 
     #[test]
-    fn overflow_int_works() {
-        let err = tokenize(&i64::MAX.to_string());
-        assert!(err.is_err());
-        assert!(matches!(
-            err.unwrap_err(),
-            RispError::ParseIntError(std::num::ParseIntError { .. })
-        ))
+    fn lone_f_is_a_symbol() {
+        assert_eq!(
+            tokenize("f").unwrap(),
+            vec![RispToken::Symbol("f".to_owned())]
+        );
+    }
+
+    #[test]
+    fn i64_bounds_are_integers() {
+        assert_eq!(
+            tokenize(&i64::MAX.to_string()).unwrap(),
+            vec![RispToken::Integer(i64::MAX)]
+        );
+        assert_eq!(
+            tokenize(&i64::MIN.to_string()).unwrap(),
+            vec![RispToken::Integer(i64::MIN)]
+        );
+    }
+
+    #[test]
+    fn overflow_int_is_a_bigint() {
+        assert_eq!(
+            tokenize(&(i64::MAX as i128 + 1).to_string()).unwrap(),
+            vec![RispToken::BigInt(BigInt::from(i64::MAX) + 1)]
+        );
+        assert_eq!(
+            tokenize("-100_000_000_000_000_000_000").unwrap(),
+            vec![RispToken::BigInt(
+                BigInt::from(-100_000_000_000i64) * 1_000_000_000
+            )]
+        );
+    }
+
+    #[test]
+    fn recognizes_ratios() {
+        assert_eq!(
+            tokenize("1/3 -2/4 +6/3").unwrap(),
+            vec![
+                RispToken::Ratio(BigRational::new(1.into(), 3.into())),
+                RispToken::Ratio(BigRational::new((-1).into(), 2.into())),
+                RispToken::Ratio(BigRational::from_integer(2.into())),
+            ]
+        );
+        assert_eq!(
+            tokenize("1 1/0"),
+            Err(error_at(1, 3, RispError::DivisionByZero))
+        );
+        assert_eq!(
+            tokenize("str/join").unwrap(),
+            vec![RispToken::Symbol("str/join".to_owned())]
+        );
     }
 
     #[test]
     fn overflow_float_works() {
         assert_eq!(
-            tokenize(&format!("11{}f", f64::MAX.to_string())).unwrap(),
+            tokenize(&format!("11{}f", f64::MAX)).unwrap(),
             vec![RispToken::Float(f64::INFINITY)]
         );
         assert_eq!(
-            tokenize(&format!("-11{}f", f64::MAX.to_string())).unwrap(),
+            tokenize(&format!("-11{}f", f64::MAX)).unwrap(),
             vec![RispToken::Float(f64::NEG_INFINITY)]
         );
     }
 
+    #[test]
+    fn recognizes_collection_brackets() {
+        assert_eq!(
+            tokenize("[1 {:a #{x}}]").unwrap(),
+            vec![
+                RispToken::LBracket,
+                RispToken::Integer(1),
+                RispToken::LBrace,
+                RispToken::Keyword("a".to_owned()),
+                RispToken::SetLBrace,
+                RispToken::Symbol("x".to_owned()),
+                RispToken::RBrace,
+                RispToken::RBrace,
+                RispToken::RBracket,
+            ]
+        );
+    }
+
+    #[test]
+    fn recognizes_regex_literals() {
+        assert_eq!(
+            tokenize(r#"(re-find #"\d+" "a12")"#).unwrap(),
+            vec![
+                RispToken::LParen,
+                RispToken::Symbol("re-find".to_owned()),
+                RispToken::RegexLiteral(r"\d+".to_owned()),
+                RispToken::StringLiteral("a12".to_owned()),
+                RispToken::RParen,
+            ]
+        );
+    }
+
     #[test]
     fn recognizes_bools() {
         assert_eq!(tokenize("true").unwrap(), vec![RispToken::Bool(true)]);
@@ -293,6 +657,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn strings_keep_their_content_exactly() {
+        assert_eq!(
+            tokenize("\"  a   b\t(c) [d]\n\" \"\"").unwrap(),
+            vec![
+                RispToken::StringLiteral("  a   b\t(c) [d]\n".to_owned()),
+                RispToken::StringLiteral("".to_owned()),
+            ]
+        );
+        assert_eq!(
+            tokenize(r#"'"a"x"#).unwrap(),
+            vec![
+                RispToken::Quote,
+                RispToken::StringLiteral("a".to_owned()),
+                RispToken::Symbol("x".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn strings_understand_escapes() {
+        assert_eq!(
+            tokenize(r#""a\nb\tc \"q\" \\ \u{e9}\u{1F600}""#).unwrap(),
+            vec![RispToken::StringLiteral(
+                "a\nb\tc \"q\" \\ \u{e9}\u{1F600}".to_owned()
+            )]
+        );
+        assert_eq!(
+            tokenize(r#""\q""#),
+            Err(error_at(1, 1, RispError::InvalidEscape(r"\q".to_owned())))
+        );
+        assert_eq!(
+            tokenize(r#""\u{zz}""#),
+            Err(error_at(1, 1, RispError::InvalidEscape(r"\u{".to_owned())))
+        );
+        assert_eq!(
+            tokenize(r#""\u{D800}""#),
+            Err(error_at(
+                1,
+                1,
+                RispError::InvalidEscape(r"\u{D800".to_owned())
+            ))
+        );
+        assert_eq!(
+            tokenize(r#""\u41""#),
+            Err(error_at(1, 1, RispError::InvalidEscape(r"\u41".to_owned())))
+        );
+    }
+
+    #[test]
+    fn unterminated_strings_are_errors() {
+        assert_eq!(
+            tokenize(r#"(str "abc)"#),
+            Err(error_at(1, 6, RispError::UnterminatedString))
+        );
+        assert_eq!(
+            tokenize(r#""abc\""#),
+            Err(error_at(1, 1, RispError::UnterminatedString))
+        );
+        assert_eq!(
+            tokenize(r#"#"a+"#),
+            Err(error_at(1, 1, RispError::UnterminatedString))
+        );
+    }
+
+    #[test]
+    fn errors_report_the_position_of_their_token() {
+        assert_eq!(
+            tokenize("(def a 1)\n(def b 2)\n(str \"abc)\n(def c 3)"),
+            Err(error_at(3, 6, RispError::UnterminatedString))
+        );
+    }
+
+    fn error_at(line: usize, column: usize, e: RispError) -> RispError {
+        RispError::InToken(Position { line, column }, Box::new(e))
+    }
+
+    #[test]
+    fn regex_literals_only_unescape_quotes() {
+        assert_eq!(
+            tokenize(r##"#"\"\d+\" \\""##).unwrap(),
+            vec![RispToken::RegexLiteral(r#""\d+" \\"#.to_owned())]
+        );
+    }
+
     #[test]
     fn recognizes_def() {
         assert_eq!(
@@ -341,4 +790,86 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn recognizes_reader_macros() {
+        assert_eq!(
+            tokenize(r#"'x `(a ~b ~@c) '(1)"#).unwrap(),
+            vec![
+                RispToken::Quote,
+                RispToken::Symbol("x".to_owned()),
+                RispToken::Quasiquote,
+                RispToken::LParen,
+                RispToken::Symbol("a".to_owned()),
+                RispToken::Unquote,
+                RispToken::Symbol("b".to_owned()),
+                RispToken::UnquoteSplicing,
+                RispToken::Symbol("c".to_owned()),
+                RispToken::RParen,
+                RispToken::Quote,
+                RispToken::LParen,
+                RispToken::Integer(1),
+                RispToken::RParen,
+            ]
+        );
+    }
+
+    #[test]
+    fn reader_macros_can_be_stuck_to_brackets() {
+        assert_eq!(
+            tokenize("`(a ~@(f b) ~[c])").unwrap(),
+            vec![
+                RispToken::Quasiquote,
+                RispToken::LParen,
+                RispToken::Symbol("a".to_owned()),
+                RispToken::UnquoteSplicing,
+                RispToken::LParen,
+                RispToken::Symbol("f".to_owned()),
+                RispToken::Symbol("b".to_owned()),
+                RispToken::RParen,
+                RispToken::Unquote,
+                RispToken::LBracket,
+                RispToken::Symbol("c".to_owned()),
+                RispToken::RBracket,
+                RispToken::RParen,
+            ]
+        );
+    }
+
+    #[test]
+    fn tokens_know_where_they_start() {
+        let (_, positions) =
+            tokenize_with_positions("(\"é\" 'ab\n  \"x\ny\" #\"z\" #{1})").unwrap();
+        assert_eq!(
+            positions
+                .iter()
+                .map(|p| (p.line, p.column))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, 1),
+                (1, 2),
+                (1, 6),
+                (1, 7),
+                (2, 3),
+                (3, 4),
+                (3, 9),
+                (3, 11),
+                (3, 12),
+                (3, 13),
+            ]
+        );
+        assert_eq!(Position::START.to_string(), "line 1, column 1");
+    }
+
+    #[test]
+    fn recognizes_stacked_reader_macros() {
+        assert_eq!(
+            tokenize(r#"''x"#).unwrap(),
+            vec![
+                RispToken::Quote,
+                RispToken::Quote,
+                RispToken::Symbol("x".to_owned()),
+            ]
+        );
+    }
 }
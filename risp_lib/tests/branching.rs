@@ -0,0 +1,133 @@
+mod common;
+
+use common::run;
+use pretty_assertions::assert_eq;
+use risp_lib::environment::RispEnv;
+use risp_lib::error::RispError;
+use risp_lib::parser::RispExp;
+use std::rc::Rc;
+
+fn string(s: &str) -> RispExp {
+    RispExp::String(s.to_owned())
+}
+
+#[test]
+fn cond_takes_the_first_truthy_branch() {
+    let mut env = RispEnv::default();
+    run(
+        r#"(def sign (fn (n) (cond (< n 0) "negative" (= n 0) "zero" else "positive")))"#,
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(run("(sign -5)", &mut env).unwrap(), string("negative"));
+    assert_eq!(run("(sign 0)", &mut env).unwrap(), string("zero"));
+    assert_eq!(run("(sign 5)", &mut env).unwrap(), string("positive"));
+    // Truthiness is the same as and/or, only nil and false are falsey.
+    assert_eq!(
+        run("(cond nil 1 false 2 0 3)", &mut env).unwrap(),
+        RispExp::Integer(3)
+    );
+    assert_eq!(run("(cond false 1)", &mut env).unwrap(), RispExp::Nil);
+}
+
+#[test]
+fn cond_only_evaluates_the_chosen_branch() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(r#"(cond true 1 (+ 1 "a") 2)"#, &mut env).unwrap(),
+        RispExp::Integer(1)
+    );
+    assert_eq!(
+        run("(cond true 1 false)", &mut env),
+        Err(RispError::MalformedClauses("cond".to_owned()))
+    );
+}
+
+#[test]
+fn case_dispatches_on_constants() {
+    let mut env = RispEnv::default();
+    run(
+        r#"(def describe (fn (x) (case x 1 "one" (2 3) "two or three" "hi" "greeting" "other")))"#,
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(run("(describe 1)", &mut env).unwrap(), string("one"));
+    assert_eq!(
+        run("(describe 3)", &mut env).unwrap(),
+        string("two or three")
+    );
+    assert_eq!(
+        run(r#"(describe "hi")"#, &mut env).unwrap(),
+        string("greeting")
+    );
+    assert_eq!(run("(describe 9)", &mut env).unwrap(), string("other"));
+    assert_eq!(
+        run("(case 9 1 2)", &mut env),
+        Err(RispError::NoMatchingClause(
+            "case".to_owned(),
+            RispExp::Integer(9)
+        ))
+    );
+}
+
+#[test]
+fn match_binds_and_destructures() {
+    let mut env = RispEnv::default();
+    run(
+        r#"(def area (fn (shape)
+             (match shape
+               '() 0
+               ('square side) (* side side)
+               ('rect w h) (* w h)
+               ('circle _) "too round"
+               (name & _) name)))"#,
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(run("(area '())", &mut env).unwrap(), RispExp::Integer(0));
+    assert_eq!(
+        run("(area '(square 3))", &mut env).unwrap(),
+        RispExp::Integer(9)
+    );
+    assert_eq!(
+        run("(area '(rect 2 5))", &mut env).unwrap(),
+        RispExp::Integer(10)
+    );
+    assert_eq!(
+        run("(area '(circle 1))", &mut env).unwrap(),
+        string("too round")
+    );
+    assert_eq!(
+        run("(area '(triangle 1 2 3))", &mut env).unwrap(),
+        RispExp::Symbol("triangle".to_owned())
+    );
+}
+
+#[test]
+fn match_compares_literals() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(r#"(match 2 1 "one" 2 "two" _ "many")"#, &mut env).unwrap(),
+        string("two")
+    );
+    assert_eq!(
+        run(r#"(match "b" "a" 1 x x)"#, &mut env).unwrap(),
+        string("b")
+    );
+    assert_eq!(
+        run(r#"(match '(1 2) (a) a)"#, &mut env),
+        Err(RispError::NoMatchingClause(
+            "match".to_owned(),
+            RispExp::List(Rc::new([RispExp::Integer(1), RispExp::Integer(2)]))
+        ))
+    );
+}
+
+#[test]
+fn failed_match_clauses_do_not_leak_bindings() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run("(match '(1 2) (a 3) a (b c) a)", &mut env).unwrap(),
+        RispExp::Symbol("a".to_owned())
+    );
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use common::{run, show};
+use pretty_assertions::assert_eq;
+use risp_lib::environment::RispEnv;
+use risp_lib::parser::RispExp;
+use std::rc::Rc;
+
+fn truthy(input: &str) -> bool {
+    run(input, &mut RispEnv::default()).unwrap() == RispExp::Bool(true)
+}
+
+#[test]
+fn collection_items_are_evaluated() {
+    let mut env = RispEnv::default();
+    run("(def x 2)", &mut env).unwrap();
+    assert_eq!(
+        run("[1 x (+ x 1)]", &mut env).unwrap(),
+        RispExp::Vector(Rc::new([
+            RispExp::Integer(1),
+            RispExp::Integer(2),
+            RispExp::Integer(3)
+        ]))
+    );
+    assert_eq!(
+        run("{:a x x :b}", &mut env).unwrap().to_string(),
+        "{:a 2, 2 :b}"
+    );
+    assert_eq!(
+        run("#{x (+ 1 1) 3}", &mut env).unwrap().to_string(),
+        "#{2, 3}"
+    );
+}
+
+#[test]
+fn quoted_collections_are_not_evaluated() {
+    assert_eq!(show("'[a (+ 1 2)]"), "['a, (f@+, 1, 2)]");
+    assert_eq!(show("`[a ~(+ 1 2) ~@'(4 5)]"), "['a, 3, 4, 5]");
+    assert_eq!(show("`{:k ~(+ 1 2)}"), "{:k 3}");
+}
+
+#[test]
+fn empty_collections() {
+    assert_eq!(show("[]"), "[]");
+    assert_eq!(show("{}"), "{}");
+    assert_eq!(show("#{}"), "#{}");
+}
+
+#[test]
+fn equality_ignores_map_and_set_order() {
+    assert!(truthy("(= {:a 1 :b 2} {:b 2 :a 1})"));
+    assert!(truthy("(= #{1 2 3} #{3 2 1})"));
+    assert!(truthy("(= [1 [2 3]] [1 [2 3]])"));
+    assert!(!truthy("(= [1 2] [2 1])"));
+    assert!(!truthy("(= [1 2] '(1 2))"));
+    assert!(!truthy("(= {:a 1} {:a 2})"));
+}
+
+#[test]
+fn collections_can_be_keys() {
+    assert_eq!(show("{[1 2] :v #{:s} :s}"), "{[1, 2] :v, #{:s} :s}");
+    assert_eq!(show("#{{:a 1} {:a 1}}"), "#{{:a 1}}");
+    assert_eq!(show("#{[1 2] [1 2] [2 1]}"), "#{[1, 2], [2, 1]}");
+    assert_eq!(show("#{0.0 -0.0}"), "#{0}");
+}
+
+#[test]
+fn later_duplicate_keys_win() {
+    assert_eq!(show("{:a 1 :a 2}"), "{:a 2}");
+}
+
+#[test]
+fn keywords_look_themselves_up() {
+    let mut env = RispEnv::default();
+    run("(def config {:host \"localhost\" :port 8080})", &mut env).unwrap();
+    assert_eq!(
+        run("(:port config)", &mut env).unwrap(),
+        RispExp::Integer(8080)
+    );
+    assert_eq!(run("(:user config)", &mut env).unwrap(), RispExp::Nil);
+    assert_eq!(
+        run("(:user config \"root\")", &mut env).unwrap(),
+        RispExp::String("root".to_owned())
+    );
+    assert_eq!(
+        run("(:a #{:a :b})", &mut env).unwrap(),
+        RispExp::Keyword("a".to_owned())
+    );
+    assert_eq!(run("(:a [:a])", &mut env).unwrap(), RispExp::Nil);
+}
@@ -0,0 +1,43 @@
+//! Helpers shared by the integration tests, each test file only uses some of them.
+#![allow(dead_code)]
+
+use risp_lib::environment::RispEnv;
+use risp_lib::error::RispError;
+use risp_lib::eval::eval;
+use risp_lib::parser::RispExp;
+use risp_lib::{parser, tokenizer};
+use std::fs;
+use std::path::PathBuf;
+
+/// Reads, parses and evaluates a single form in env.
+pub fn run(input: &str, env: &mut RispEnv) -> Result<RispExp, RispError> {
+    let tokens = tokenizer::tokenize(input)?;
+    let expr = parser::parse(&tokens)?;
+    eval(&expr, env)
+}
+
+/// Like [run], in an env of its own.
+pub fn run_fresh(input: &str) -> Result<RispExp, RispError> {
+    run(input, &mut RispEnv::default())
+}
+
+/// The printed value of input, for comparing values that are awkward to build by hand.
+pub fn show(input: &str) -> String {
+    run_fresh(input).unwrap().to_string()
+}
+
+pub fn error(input: &str) -> RispError {
+    run_fresh(input).unwrap_err()
+}
+
+/// A fresh directory for one test, holding the given (relative path, contents) files.
+pub fn temp_dir(test: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("risp_{}_{}", test, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    for (path, contents) in files {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+    dir
+}
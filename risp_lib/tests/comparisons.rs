@@ -0,0 +1,79 @@
+mod common;
+
+use common::run_fresh;
+use pretty_assertions::assert_eq;
+use risp_lib::error::{RispError, INCOMPARABLE_TYPES};
+use risp_lib::parser::RispExp;
+
+#[test]
+fn comparisons_check_every_adjacent_pair() {
+    for (input, expected) in [
+        ("(< 1 2 3 4)", true),
+        ("(< 1 2 2 4)", false),
+        ("(<= 1 2 2 4)", true),
+        ("(> 4 3 2.5 1)", true),
+        ("(> 4 3 5)", false),
+        ("(>= 4 4 3 3)", true),
+        ("(= 2 2 2)", true),
+        ("(= 2 2 3)", false),
+        ("(< 1)", true),
+        ("(= 1)", true),
+    ] {
+        assert_eq!(
+            run_fresh(input).unwrap(),
+            RispExp::Bool(expected),
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn strings_and_chars_are_ordered() {
+    assert_eq!(
+        run_fresh(r#"(< "a" "b" "c")"#).unwrap(),
+        RispExp::Bool(true)
+    );
+    assert_eq!(run_fresh(r#"(> \a \b)"#).unwrap(), RispExp::Bool(false));
+}
+
+#[test]
+fn equality_works_on_any_type() {
+    assert_eq!(
+        run_fresh(r#"(= "a" "a" "a")"#).unwrap(),
+        RispExp::Bool(true)
+    );
+    assert_eq!(run_fresh(r#"(= 1 "1")"#).unwrap(), RispExp::Bool(false));
+    assert_eq!(run_fresh("(= '(1 2) '(1 2))").unwrap(), RispExp::Bool(true));
+}
+
+#[test]
+fn no_args_is_an_arity_error() {
+    for op in ["<", "<=", ">", ">=", "="] {
+        assert!(
+            matches!(
+                run_fresh(&format!("({})", op)),
+                Err(RispError::ArityMismatch(_))
+            ),
+            "{}",
+            op
+        );
+    }
+}
+
+#[test]
+fn ordering_incomparable_types_is_a_type_error() {
+    for input in [
+        r#"(< 1 "a")"#,
+        r#"(>= "a" \a)"#,
+        r#"(< 2 1 "a")"#,
+        "(> nil 1)",
+    ] {
+        assert_eq!(
+            run_fresh(input),
+            Err(RispError::TypeError(INCOMPARABLE_TYPES)),
+            "{}",
+            input
+        );
+    }
+}
@@ -1,18 +1,37 @@
+mod common;
+
+use common::run;
 use risp_lib::environment::RispEnv;
-use risp_lib::eval::eval;
+use risp_lib::error::RispError;
 use risp_lib::parser::RispExp;
-use risp_lib::{parser, tokenizer};
 
 #[test]
 fn def() {
     let mut env = RispEnv::default();
-    let tokens = tokenizer::tokenize(r#"(def one 1)"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Nil);
-    let tokens = tokenizer::tokenize(r#"(def two 2)"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Nil);
-    let tokens = tokenizer::tokenize(r#"(+ one two)"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Integer(3));
+    assert_eq!(run(r#"(def one 1)"#, &mut env).unwrap(), RispExp::Nil);
+    assert_eq!(run(r#"(def two 2)"#, &mut env).unwrap(), RispExp::Nil);
+    assert_eq!(
+        run(r#"(+ one two)"#, &mut env).unwrap(),
+        RispExp::Integer(3)
+    );
+}
+
+#[test]
+fn def_can_redefine_a_name() {
+    let mut env = RispEnv::default();
+    for input in ["(def one 1)", "(def one (+ one 1))"] {
+        assert_eq!(run(input, &mut env).unwrap(), RispExp::Nil);
+    }
+    assert_eq!(env.get("one").unwrap(), RispExp::Integer(2));
+}
+
+#[test]
+fn def_of_a_builtin_name_is_an_error() {
+    let mut env = RispEnv::default();
+    for name in ["+", "if", "def"] {
+        assert_eq!(
+            run(&format!("(def {} 1)", name), &mut env),
+            Err(RispError::InvalidName(name.to_owned()))
+        );
+    }
 }
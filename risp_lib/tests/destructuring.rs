@@ -0,0 +1,166 @@
+mod common;
+
+use common::run;
+use pretty_assertions::assert_eq;
+use risp_lib::environment::RispEnv;
+use risp_lib::error::{RispError, MALFORMED_PATTERN};
+use risp_lib::parser::RispExp;
+use std::rc::Rc;
+
+fn ints(xs: &[i64]) -> RispExp {
+    RispExp::List(xs.iter().map(|x| RispExp::Integer(*x)).collect())
+}
+
+#[test]
+fn fn_params_destructure_lists() {
+    let mut env = RispEnv::default();
+    run("(def f (fn ((a b) c) (+ a b c)))", &mut env).unwrap();
+    assert_eq!(run("(f '(1 2) 3)", &mut env).unwrap(), RispExp::Integer(6));
+}
+
+#[test]
+fn patterns_nest_and_take_rest() {
+    let mut env = RispEnv::default();
+    run("(def f (fn ((a (b c) & more)) more))", &mut env).unwrap();
+    assert_eq!(run("(f '(1 (2 3) 4 5))", &mut env).unwrap(), ints(&[4, 5]));
+    run("(def g (fn ((a (b c) & more)) (+ a b c)))", &mut env).unwrap();
+    assert_eq!(
+        run("(g '(1 (2 3)))", &mut env).unwrap(),
+        RispExp::Integer(6)
+    );
+}
+
+#[test]
+fn rest_and_optional_params_destructure() {
+    let mut env = RispEnv::default();
+    run("(def f (fn (& (a b)) (+ a b)))", &mut env).unwrap();
+    assert_eq!(run("(f 1 2)", &mut env).unwrap(), RispExp::Integer(3));
+    run("(def g (fn (&opt ((a b) '(1 2))) (+ a b)))", &mut env).unwrap();
+    assert_eq!(run("(g)", &mut env).unwrap(), RispExp::Integer(3));
+    assert_eq!(run("(g '(3 4))", &mut env).unwrap(), RispExp::Integer(7));
+}
+
+#[test]
+fn let_forms_destructure() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run("(let ((a b) '(1 2) c 3) (+ a b c))", &mut env).unwrap(),
+        RispExp::Integer(6)
+    );
+    assert_eq!(
+        run(
+            "(let* ((a & more) '(1 2 3) (b c) more) (+ a b c))",
+            &mut env
+        )
+        .unwrap(),
+        RispExp::Integer(6)
+    );
+}
+
+#[test]
+fn vector_patterns_and_values() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run("(let ([a b] [1 2] (c d) [3 4]) (+ a b c d))", &mut env).unwrap(),
+        RispExp::Integer(10)
+    );
+    assert_eq!(
+        run("(let ([a & more] '(1 2 3)) more)", &mut env).unwrap(),
+        ints(&[2, 3])
+    );
+    run("(def f (fn [[a b] c] (+ a b c)))", &mut env).unwrap();
+    assert_eq!(run("(f [1 2] 3)", &mut env).unwrap(), RispExp::Integer(6));
+    assert_eq!(run("(f '(1 2) 3)", &mut env).unwrap(), RispExp::Integer(6));
+    assert_eq!(
+        run("(match [1 [2 3]] (a [b]) a [a [b c]] (+ a b c))", &mut env).unwrap(),
+        RispExp::Integer(6)
+    );
+}
+
+#[test]
+fn map_patterns_look_up_keys() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(
+            "(let ({a :a [b c] :bc} {:a 1 :bc [2 3]}) (+ a b c))",
+            &mut env
+        )
+        .unwrap(),
+        RispExp::Integer(6)
+    );
+    assert_eq!(
+        run("(let ({a :a b \"b\"} {:a 1}) b)", &mut env).unwrap(),
+        RispExp::Nil
+    );
+    run("(def f (fn (x &opt ({y :y} {:y 2})) (+ x y)))", &mut env).unwrap();
+    assert_eq!(run("(f 1)", &mut env).unwrap(), RispExp::Integer(3));
+    assert_eq!(run("(f 1 {:y 5})", &mut env).unwrap(), RispExp::Integer(6));
+    assert_eq!(
+        run("(match {:a 1} {x :b} x {x :a} (+ x 1))", &mut env).unwrap(),
+        RispExp::Integer(2)
+    );
+    assert_eq!(
+        run("(f 1 [5])", &mut env),
+        Err(RispError::PatternMismatch(
+            RispExp::Map(Box::new(
+                [(
+                    RispExp::Symbol("y".to_owned()),
+                    RispExp::Keyword("y".to_owned())
+                )]
+                .into_iter()
+                .collect()
+            )),
+            RispExp::Vector(Rc::new([RispExp::Integer(5)]))
+        ))
+    );
+}
+
+#[test]
+fn mismatch_reports_the_innermost_pattern() {
+    let mut env = RispEnv::default();
+    run("(def f (fn ((a (b c))) a))", &mut env).unwrap();
+    assert_eq!(
+        run("(f '(1 (2)))", &mut env),
+        Err(RispError::PatternMismatch(
+            RispExp::List(Rc::new([
+                RispExp::Symbol("b".to_owned()),
+                RispExp::Symbol("c".to_owned())
+            ])),
+            ints(&[2])
+        ))
+    );
+    assert_eq!(
+        run("(f '(1 2))", &mut env),
+        Err(RispError::PatternMismatch(
+            RispExp::List(Rc::new([
+                RispExp::Symbol("b".to_owned()),
+                RispExp::Symbol("c".to_owned())
+            ])),
+            RispExp::Integer(2)
+        ))
+    );
+}
+
+#[test]
+fn malformed_patterns_are_errors() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run("(let ((a 1) '(1 1)) a)", &mut env),
+        Err(RispError::UnexpectedExpr(
+            RispExp::Integer(1),
+            MALFORMED_PATTERN.to_owned()
+        ))
+    );
+    assert_eq!(
+        run("(let ((a & b c) '(1 2 3)) a)", &mut env),
+        Err(RispError::UnexpectedExpr(
+            RispExp::List(Rc::new([
+                RispExp::Symbol("a".to_owned()),
+                RispExp::Symbol("&".to_owned()),
+                RispExp::Symbol("b".to_owned()),
+                RispExp::Symbol("c".to_owned())
+            ])),
+            MALFORMED_PATTERN.to_owned()
+        ))
+    );
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use common::run;
+use pretty_assertions::assert_eq;
+use risp_lib::environment::RispEnv;
+use risp_lib::parser::RispExp;
+
+#[test]
+fn do_returns_its_last_value() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run("(do (def x 1) (def y 2) (+ x y))", &mut env).unwrap(),
+        RispExp::Integer(3)
+    );
+    assert_eq!(env.get("x").unwrap(), RispExp::Integer(1));
+    assert_eq!(run("(do)", &mut env).unwrap(), RispExp::Nil);
+}
+
+#[test]
+fn do_stops_at_the_first_error() {
+    let mut env = RispEnv::default();
+    assert!(run(r#"(do (+ 1 "a") (def x 1))"#, &mut env).is_err());
+    assert!(!env.has_interned_var("x"));
+}
+
+#[test]
+fn fn_takes_an_implicit_body() {
+    let mut env = RispEnv::default();
+    run(
+        "(def area (fn (w h) (def last-w w) (def last-h h) (* w h)))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(run("(area 3 4)", &mut env).unwrap(), RispExp::Integer(12));
+    // The intermediate forms ran in the call's scope.
+    assert!(!env.has_interned_var("last-w"));
+}
+
+#[test]
+fn fn_body_can_be_an_atom() {
+    let mut env = RispEnv::default();
+    run("(def id (fn (x) x))", &mut env).unwrap();
+    assert_eq!(run("(id 7)", &mut env).unwrap(), RispExp::Integer(7));
+}
+
+#[test]
+fn defmacro_takes_an_implicit_body() {
+    let mut env = RispEnv::default();
+    run("(defmacro twice (x) (def ignored 1) `(do ~x ~x))", &mut env).unwrap();
+    assert_eq!(
+        run("(twice (+ 1 2))", &mut env).unwrap(),
+        RispExp::Integer(3)
+    );
+}
+
+#[test]
+fn let_body_ends_in_tail_position() {
+    let mut env = RispEnv::default();
+    run(
+        "(def count-down (fn (n) (let (m (- n 1)) (def ignored m) (if (= n 0) 0 (count-down m)))))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(
+        run("(count-down 20000)", &mut env).unwrap(),
+        RispExp::Integer(0)
+    );
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use common::run;
+use pretty_assertions::assert_eq;
+use risp_lib::environment::RispEnv;
+use risp_lib::error::RispError;
+use risp_lib::parser::RispExp;
+use std::rc::Rc;
+
+#[test]
+fn uncaught_throws_surface_as_errors() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(r#"(throw "boom" 42)"#, &mut env),
+        Err(RispError::Thrown("boom".to_owned(), RispExp::Integer(42)))
+    );
+    assert_eq!(
+        run(r#"(try (throw "boom") (catch type-error e 1))"#, &mut env),
+        Err(RispError::Thrown("boom".to_owned(), RispExp::Nil))
+    );
+}
+
+#[test]
+fn catch_binds_the_error_value() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(
+            r#"(try (+ 1 (throw "bad" '(1 2))) (catch thrown e e))"#,
+            &mut env
+        )
+        .unwrap(),
+        RispExp::List(Rc::new([
+            RispExp::Symbol("thrown".to_owned()),
+            RispExp::String("bad".to_owned()),
+            RispExp::List(Rc::new([RispExp::Integer(1), RispExp::Integer(2)])),
+        ]))
+    );
+    assert_eq!(
+        run(
+            r#"(try (throw "bad" 7) (catch _ (kind message payload) payload))"#,
+            &mut env
+        )
+        .unwrap(),
+        RispExp::Integer(7)
+    );
+}
+
+#[test]
+fn interpreter_errors_are_caught_by_kind() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(
+            r#"(try (+ 1 "a") (catch arity-mismatch e "arity") (catch type-error e "type"))"#,
+            &mut env
+        )
+        .unwrap(),
+        RispExp::String("type".to_owned())
+    );
+    assert_eq!(
+        run(
+            "(try ((fn (x) x)) (catch (type-error arity-mismatch) e (let ((kind & _) e) kind)))",
+            &mut env
+        )
+        .unwrap(),
+        RispExp::Symbol("arity-mismatch".to_owned())
+    );
+    assert_eq!(
+        run("(try (recur 1) (catch _ e 0))", &mut env).unwrap(),
+        RispExp::Integer(0)
+    );
+}
+
+#[test]
+fn try_without_error_returns_the_body() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run("(try (def x 1) (+ x 1) (catch _ e 0))", &mut env).unwrap(),
+        RispExp::Integer(2)
+    );
+}
+
+#[test]
+fn finally_always_runs() {
+    let mut env = RispEnv::default();
+    run("(def log (atom 0))", &mut env).unwrap();
+    assert_eq!(
+        run("(try 1 (finally (swap! log + 1)))", &mut env).unwrap(),
+        RispExp::Integer(1)
+    );
+    assert_eq!(
+        run(
+            r#"(try (throw "x") (catch _ e 2) (finally (swap! log + 10)))"#,
+            &mut env
+        )
+        .unwrap(),
+        RispExp::Integer(2)
+    );
+    assert_eq!(
+        run(r#"(try (throw "x") (finally (swap! log + 100)))"#, &mut env),
+        Err(RispError::Thrown("x".to_owned(), RispExp::Nil))
+    );
+    assert_eq!(run("(deref log)", &mut env).unwrap(), RispExp::Integer(111));
+}
+
+#[test]
+fn errors_in_handlers_propagate() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(r#"(try (throw "a") (catch _ e (throw "b")))"#, &mut env),
+        Err(RispError::Thrown("b".to_owned(), RispExp::Nil))
+    );
+    assert_eq!(
+        run(
+            r#"(try (throw "a") (catch _ e (throw "b")) (finally (throw "c")))"#,
+            &mut env
+        ),
+        Err(RispError::Thrown("c".to_owned(), RispExp::Nil))
+    );
+}
+
+#[test]
+fn malformed_try_is_an_error() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run("(try 1 (finally 2) (catch _ e 3))", &mut env),
+        Err(RispError::MalformedTryExpression)
+    );
+    assert_eq!(
+        run("(try 1 (catch _))", &mut env),
+        Err(RispError::MalformedTryExpression)
+    );
+}
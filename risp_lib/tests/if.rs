@@ -1,22 +1,74 @@
+mod common;
+
+use common::run;
 use risp_lib::environment::RispEnv;
-use risp_lib::eval::eval;
 use risp_lib::parser::RispExp;
-use risp_lib::{parser, tokenizer};
 
 #[test]
 fn if_integration_test() {
     let mut env = RispEnv::default();
-    let tokens = tokenizer::tokenize(r#"(if true "true" "false")"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
     assert_eq!(
-        eval(&expr, &mut env).unwrap(),
+        run(r#"(if true "true" "false")"#, &mut env).unwrap(),
         RispExp::String("true".to_owned())
     );
 
-    let tokens = tokenizer::tokenize(r#"(if false "true" "false")"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
     assert_eq!(
-        eval(&expr, &mut env).unwrap(),
+        run(r#"(if false "true" "false")"#, &mut env).unwrap(),
         RispExp::String("false".to_owned())
     );
 }
+
+#[test]
+fn if_only_evaluates_chosen_branch() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(r#"(if true 1 (+ 1 "not a number"))"#, &mut env).unwrap(),
+        RispExp::Integer(1)
+    );
+
+    assert_eq!(
+        run(
+            r#"(if false (def taken true) (def skipped true))"#,
+            &mut env
+        )
+        .unwrap(),
+        RispExp::Nil
+    );
+    assert!(!env.has_interned_var("taken"));
+    assert!(env.has_interned_var("skipped"));
+}
+
+#[test]
+fn if_terminates_recursion() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(
+            r#"(def fact (fn (n) (if (= n 0) 1 (* n (fact (- n 1))))))"#,
+            &mut env
+        )
+        .unwrap(),
+        RispExp::Nil
+    );
+
+    assert_eq!(run(r#"(fact 5)"#, &mut env).unwrap(), RispExp::Integer(120));
+}
+
+#[test]
+fn if_treats_only_nil_and_false_as_false() {
+    let mut env = RispEnv::default();
+    for (input, expected) in [
+        ("(if nil 1 2)", 2),
+        ("(if false 1 2)", 2),
+        ("(if 0 1 2)", 1),
+        (r#"(if "" 1 2)"#, 1),
+        ("(if '() 1 2)", 1),
+        ("(if '(1) 1 2)", 1),
+    ] {
+        assert_eq!(
+            run(input, &mut env).unwrap(),
+            RispExp::Integer(expected),
+            "{}",
+            input
+        );
+    }
+}
@@ -0,0 +1,82 @@
+mod common;
+
+use common::run;
+use pretty_assertions::assert_eq;
+use risp_lib::environment::RispEnv;
+use risp_lib::error::{RispError, KEYWORD_CALL_ARGS};
+use risp_lib::parser::RispExp;
+use std::rc::Rc;
+
+fn keyword(name: &str) -> RispExp {
+    RispExp::Keyword(name.to_owned())
+}
+
+#[test]
+fn keywords_evaluate_to_themselves() {
+    let mut env = RispEnv::default();
+    assert_eq!(run(":color", &mut env).unwrap(), keyword("color"));
+    assert_eq!(
+        run("'(:a :b)", &mut env).unwrap(),
+        RispExp::List(Rc::new([keyword("a"), keyword("b")]))
+    );
+    assert_eq!(run(":color", &mut env).unwrap().to_string(), ":color");
+}
+
+#[test]
+fn keywords_are_not_symbols() {
+    let mut env = RispEnv::default();
+    run("(def color 1)", &mut env).unwrap();
+    assert_eq!(run(":color", &mut env).unwrap(), keyword("color"));
+    assert_eq!(
+        run("(def :color 1)", &mut env),
+        Err(RispError::MalformedDefExpression)
+    );
+}
+
+#[test]
+fn keywords_compare_by_name() {
+    let mut env = RispEnv::default();
+    assert_eq!(run("(= :a :a)", &mut env).unwrap(), RispExp::Bool(true));
+    assert_eq!(run("(= :a :b)", &mut env).unwrap(), RispExp::Bool(false));
+    assert_eq!(
+        run(r#"(= :a "a")"#, &mut env).unwrap(),
+        RispExp::Bool(false)
+    );
+    assert_eq!(run("(= :a 'a)", &mut env).unwrap(), RispExp::Bool(false));
+}
+
+#[test]
+fn keywords_work_in_case_and_match() {
+    let mut env = RispEnv::default();
+    run(
+        r#"(def describe (fn (x) (case x :red "warm" (:blue :green) "cool" "unknown")))"#,
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(
+        run("(describe :green)", &mut env).unwrap(),
+        RispExp::String("cool".to_owned())
+    );
+    assert_eq!(
+        run("(describe :pink)", &mut env).unwrap(),
+        RispExp::String("unknown".to_owned())
+    );
+    assert_eq!(
+        run("(match '(:ok 5) (:error e) e (:ok v) v)", &mut env).unwrap(),
+        RispExp::Integer(5)
+    );
+}
+
+#[test]
+fn keywords_called_as_functions() {
+    let mut env = RispEnv::default();
+    assert_eq!(run("(:a nil)", &mut env).unwrap(), RispExp::Nil);
+    assert_eq!(run("(:a nil 5)", &mut env).unwrap(), RispExp::Integer(5));
+    assert_eq!(
+        run("(:a)", &mut env),
+        Err(RispError::UnexpectedExpr(
+            keyword("a"),
+            KEYWORD_CALL_ARGS.to_owned()
+        ))
+    );
+}
@@ -1,16 +1,89 @@
+mod common;
+
+use common::run;
 use risp_lib::environment::RispEnv;
-use risp_lib::eval::eval;
 use risp_lib::parser::RispExp;
-use risp_lib::{parser, tokenizer};
 
 #[test]
 fn lambda_works() {
     let mut env = RispEnv::default();
-    let tokens = tokenizer::tokenize(r#"(def addOne (fn (x) (+ 1 x)))"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Nil);
+    assert_eq!(
+        run(r#"(def addOne (fn (x) (+ 1 x)))"#, &mut env).unwrap(),
+        RispExp::Nil
+    );
+
+    assert_eq!(
+        run(r#"(addOne 37)"#, &mut env).unwrap(),
+        RispExp::Integer(38)
+    );
+}
+
+#[test]
+fn closures_capture_their_env() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(r#"(def make_adder (fn (x) (fn (y) (+ x y))))"#, &mut env).unwrap(),
+        RispExp::Nil
+    );
+
+    assert_eq!(
+        run(r#"(def add5 (make_adder 5))"#, &mut env).unwrap(),
+        RispExp::Nil
+    );
+    assert_eq!(
+        run(r#"(def add10 (make_adder 10))"#, &mut env).unwrap(),
+        RispExp::Nil
+    );
+
+    assert_eq!(run(r#"(add5 37)"#, &mut env).unwrap(), RispExp::Integer(42));
+    assert_eq!(
+        run(r#"(add10 37)"#, &mut env).unwrap(),
+        RispExp::Integer(47)
+    );
+}
+
+#[test]
+fn closures_compose() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(
+            r#"(def compose (fn (outer inner) (fn (x) (outer (inner x)))))"#,
+            &mut env
+        )
+        .unwrap(),
+        RispExp::Nil
+    );
+
+    assert_eq!(
+        run(
+            r#"(def inc_then_double (compose (fn (x) (* x 2)) (fn (x) (+ x 1))))"#,
+            &mut env
+        )
+        .unwrap(),
+        RispExp::Nil
+    );
+
+    assert_eq!(
+        run(r#"(inc_then_double 20)"#, &mut env).unwrap(),
+        RispExp::Integer(42)
+    );
+}
+
+#[test]
+fn closures_are_lexically_scoped() {
+    let mut env = RispEnv::default();
+    assert_eq!(run(r#"(def x 1)"#, &mut env).unwrap(), RispExp::Nil);
+    assert_eq!(
+        run(r#"(def get_x (fn () (+ x)))"#, &mut env).unwrap(),
+        RispExp::Nil
+    );
+    assert_eq!(
+        run(r#"(def shadow_x (fn (x) (get_x)))"#, &mut env).unwrap(),
+        RispExp::Nil
+    );
 
-    let tokens = tokenizer::tokenize(r#"(addOne 37)"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Integer(38));
+    assert_eq!(
+        run(r#"(shadow_x 100)"#, &mut env).unwrap(),
+        RispExp::Integer(1)
+    );
 }
@@ -0,0 +1,92 @@
+mod common;
+
+use common::run;
+use pretty_assertions::assert_eq;
+use risp_lib::environment::RispEnv;
+use risp_lib::error::RispError;
+use risp_lib::parser::RispExp;
+
+#[test]
+fn let_binds_in_a_new_scope() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run("(let (x 1 y 2) (+ x y))", &mut env).unwrap(),
+        RispExp::Integer(3)
+    );
+    // Nothing leaks into the enclosing env.
+    assert!(!env.has_interned_var("x"));
+    assert!(!env.has_interned_var("y"));
+}
+
+#[test]
+fn let_evaluates_inits_in_the_enclosing_scope() {
+    let mut env = RispEnv::default();
+    run("(def x 10)", &mut env).unwrap();
+    assert_eq!(
+        run("(let (x 1 y x) y)", &mut env).unwrap(),
+        RispExp::Integer(10)
+    );
+}
+
+#[test]
+fn let_star_binds_sequentially() {
+    let mut env = RispEnv::default();
+    run("(def x 10)", &mut env).unwrap();
+    assert_eq!(
+        run("(let* (x 1 y (+ x 1)) y)", &mut env).unwrap(),
+        RispExp::Integer(2)
+    );
+}
+
+#[test]
+fn let_shadows_and_nests() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run("(let (x 1) (let (x 2) x))", &mut env).unwrap(),
+        RispExp::Integer(2)
+    );
+    assert_eq!(
+        run("(let (x 1) (let (y 2) (+ x y)))", &mut env).unwrap(),
+        RispExp::Integer(3)
+    );
+}
+
+#[test]
+fn let_bindings_are_captured_by_closures() {
+    let mut env = RispEnv::default();
+    run("(def add-n (let (n 5) (fn (x) (+ x n))))", &mut env).unwrap();
+    assert_eq!(run("(add-n 1)", &mut env).unwrap(), RispExp::Integer(6));
+}
+
+#[test]
+fn letrec_allows_mutual_recursion() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(
+            "(letrec (is-even (fn (n) (if (= n 0) true (is-odd (- n 1)))) \
+                      is-odd (fn (n) (if (= n 0) false (is-even (- n 1))))) \
+               (is-even 10))",
+            &mut env
+        )
+        .unwrap(),
+        RispExp::Bool(true)
+    );
+    assert!(!env.has_interned_var("is-even"));
+}
+
+#[test]
+fn malformed_bindings_are_errors() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run("(let (x) x)", &mut env),
+        Err(RispError::MalformedBindingForm("let".to_owned()))
+    );
+    assert_eq!(
+        run("(let* (1 2) 3)", &mut env),
+        Err(RispError::MalformedBindingForm("let*".to_owned()))
+    );
+    assert_eq!(
+        run("(letrec x x)", &mut env),
+        Err(RispError::MalformedBindingForm("letrec".to_owned()))
+    );
+}
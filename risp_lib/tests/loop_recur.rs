@@ -0,0 +1,92 @@
+mod common;
+
+use common::run;
+use pretty_assertions::assert_eq;
+use risp_lib::environment::RispEnv;
+use risp_lib::error::RispError;
+use risp_lib::parser::RispExp;
+
+#[test]
+fn loop_recur_iterates_in_constant_stack() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(
+            "(loop (i 0 acc 0) (if (= i 100000) acc (recur (+ i 1) (+ acc 2))))",
+            &mut env
+        )
+        .unwrap(),
+        RispExp::Integer(200000)
+    );
+}
+
+#[test]
+fn loop_binds_sequentially_and_destructures() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(
+            "(loop ((a b) '(1 2) c (+ a b)) (if (> c 10) c (recur '(1 2) (* c 2))))",
+            &mut env
+        )
+        .unwrap(),
+        RispExp::Integer(12)
+    );
+}
+
+#[test]
+fn recur_reenters_the_enclosing_fn() {
+    let mut env = RispEnv::default();
+    run(
+        "(def count-down (fn (n) (if (= n 0) \"done\" (recur (- n 1)))))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(
+        run("(count-down 100000)", &mut env).unwrap(),
+        RispExp::String("done".to_owned())
+    );
+}
+
+#[test]
+fn recur_targets_the_innermost_loop() {
+    let mut env = RispEnv::default();
+    run(
+        "(def f (fn (n) (loop (i 0) (if (< i n) (recur (+ i 1)) (* i 10)))))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(run("(f 5)", &mut env).unwrap(), RispExp::Integer(50));
+    assert_eq!(
+        run(
+            "(+ 1 (loop (i 0) (if (< i 3) (recur (+ i 1)) i)))",
+            &mut env
+        )
+        .unwrap(),
+        RispExp::Integer(4)
+    );
+}
+
+#[test]
+fn recur_outside_tail_position_is_an_error() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run("(recur 1)", &mut env),
+        Err(RispError::RecurOutsideTailPosition)
+    );
+    assert_eq!(
+        run("(loop (i 0) (+ 1 (recur i)))", &mut env),
+        Err(RispError::RecurOutsideTailPosition)
+    );
+    assert_eq!(
+        run("(loop (i 0) (do (recur i) i))", &mut env),
+        Err(RispError::RecurOutsideTailPosition)
+    );
+}
+
+#[test]
+fn recur_must_match_the_loop_bindings() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run("(loop (i 0 j 0) (recur 1))", &mut env),
+        Err(RispError::RecurArityMismatch(2, 1))
+    );
+}
@@ -0,0 +1,140 @@
+mod common;
+
+use common::run;
+use pretty_assertions::assert_eq;
+use risp_lib::environment::RispEnv;
+use risp_lib::error::RispError;
+use risp_lib::parser::{RispBuiltinFunction, RispExp, RispFunction};
+use std::rc::Rc;
+
+#[test]
+fn defmacro_defines_control_structures() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(
+            "(defmacro unless (test then else) `(if ~test ~else ~then))",
+            &mut env
+        )
+        .unwrap(),
+        RispExp::Nil
+    );
+    assert_eq!(
+        run(r#"(unless (< 1 2) "no" "yes")"#, &mut env).unwrap(),
+        RispExp::String("yes".to_owned())
+    );
+    // Only the chosen branch is evaluated, same as the if it expands into.
+    assert_eq!(
+        run(r#"(unless false "yes" (+ 1 "no"))"#, &mut env).unwrap(),
+        RispExp::String("yes".to_owned())
+    );
+}
+
+#[test]
+fn macros_receive_unevaluated_args() {
+    let mut env = RispEnv::default();
+    run(
+        "(defmacro when (test body) `(if ~test ~body nil))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(
+        run("(when false (def launched true))", &mut env).unwrap(),
+        RispExp::Nil
+    );
+    assert!(!env.has_interned_var("launched"));
+    assert_eq!(
+        run("(when true (+ 40 2))", &mut env).unwrap(),
+        RispExp::Integer(42)
+    );
+}
+
+#[test]
+fn macros_can_be_named_with_punctuation() {
+    let mut env = RispEnv::default();
+    run("(def inc (fn (x) (+ x 1)))", &mut env).unwrap();
+    run("(defmacro -> (x f) `(~f ~x))", &mut env).unwrap();
+    assert_eq!(run("(-> 41 inc)", &mut env).unwrap(), RispExp::Integer(42));
+}
+
+#[test]
+fn macro_params_are_written_like_fn_params() {
+    let mut env = RispEnv::default();
+    run("(defmacro first_of [x & more] x)", &mut env).unwrap();
+    assert_eq!(
+        run("(first_of 1 (+ 1 \"a\"))", &mut env).unwrap(),
+        RispExp::Integer(1)
+    );
+    run(
+        "(defmacro unless (test then &opt else) `(if ~test ~else ~then))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(
+        run("(unless false 1)", &mut env).unwrap(),
+        RispExp::Integer(1)
+    );
+    assert_eq!(run("(unless true 1)", &mut env).unwrap(), RispExp::Nil);
+}
+
+#[test]
+fn macroexpand_shows_the_expansion() {
+    let mut env = RispEnv::default();
+    run(
+        "(defmacro unless (test then else) `(if ~test ~else ~then))",
+        &mut env,
+    )
+    .unwrap();
+    run(
+        "(defmacro if_not (test then else) `(unless ~test ~then ~else))",
+        &mut env,
+    )
+    .unwrap();
+
+    assert_eq!(
+        run("(macroexpand-1 '(if_not c 1 2))", &mut env).unwrap(),
+        RispExp::List(Rc::new([
+            RispExp::Symbol("unless".to_owned()),
+            RispExp::Symbol("c".to_owned()),
+            RispExp::Integer(1),
+            RispExp::Integer(2),
+        ]))
+    );
+    assert_eq!(
+        run("(macroexpand '(if_not c 1 2))", &mut env).unwrap(),
+        RispExp::List(Rc::new([
+            RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::If)),
+            RispExp::Symbol("c".to_owned()),
+            RispExp::Integer(2),
+            RispExp::Integer(1),
+        ]))
+    );
+    assert_eq!(
+        run("(macroexpand '(+ 1 2))", &mut env).unwrap(),
+        RispExp::List(Rc::new([
+            RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
+            RispExp::Integer(1),
+            RispExp::Integer(2),
+        ]))
+    );
+}
+
+#[test]
+fn malformed_defmacro_is_an_error() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run("(defmacro 1 (x) x)", &mut env).unwrap_err(),
+        RispError::MalformedDefmacroExpression
+    );
+    assert_eq!(
+        run("(defmacro if (x) x)", &mut env).unwrap_err(),
+        RispError::MalformedDefmacroExpression
+    );
+    assert_eq!(
+        run("(defmacro quote (x) x)", &mut env).unwrap_err(),
+        RispError::MalformedDefmacroExpression
+    );
+    assert_eq!(
+        run("(defmacro name_only)", &mut env).unwrap_err(),
+        RispError::ArityMismatch(RispFunction::Builtin(RispBuiltinFunction::Defmacro))
+    );
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use common::run;
+use pretty_assertions::assert_eq;
+use risp_lib::environment::RispEnv;
+use risp_lib::error::{RispError, MALFORMED_FN_CLAUSE};
+use risp_lib::parser::RispExp;
+use std::rc::Rc;
+
+#[test]
+fn clause_is_chosen_by_arity() {
+    let mut env = RispEnv::default();
+    run(
+        "(def add (fn ([] 0) ([x] x) ([x y] (+ x y)) ([x y & more] (+ x y 100))))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(run("(add)", &mut env).unwrap(), RispExp::Integer(0));
+    assert_eq!(run("(add 1)", &mut env).unwrap(), RispExp::Integer(1));
+    assert_eq!(run("(add 1 2)", &mut env).unwrap(), RispExp::Integer(3));
+    assert_eq!(run("(add 1 2 3)", &mut env).unwrap(), RispExp::Integer(103));
+}
+
+#[test]
+fn clauses_can_call_each_other() {
+    let mut env = RispEnv::default();
+    run(
+        "(def greet (fn ([name] (greet \"Hello\" name)) ([greeting name] (def said greeting) name)))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(
+        run(r#"(greet "Ada")"#, &mut env).unwrap(),
+        RispExp::String("Ada".to_owned())
+    );
+}
+
+#[test]
+fn clauses_have_implicit_bodies_and_close_over_their_env() {
+    let mut env = RispEnv::default();
+    run(
+        "(def make (fn (n) (fn ([] n) ([x] (def ignored x) (+ x n)))))",
+        &mut env,
+    )
+    .unwrap();
+    run("(def add5 (make 5))", &mut env).unwrap();
+    assert_eq!(run("(add5)", &mut env).unwrap(), RispExp::Integer(5));
+    assert_eq!(run("(add5 1)", &mut env).unwrap(), RispExp::Integer(6));
+}
+
+#[test]
+fn arity_mismatch_reports_accepted_arities() {
+    let mut env = RispEnv::default();
+    run(
+        "(def f (fn ([x] x) ([x y &opt z] x) ([a b c d & more] a)))",
+        &mut env,
+    )
+    .unwrap();
+    let err = run("(f)", &mut env).unwrap_err();
+    assert!(matches!(err, RispError::ArityMismatch(_)));
+    assert!(
+        err.to_string()
+            .ends_with(", it accepts 1, 2 to 3, 4 or more args"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn list_params_starting_with_a_pattern_are_not_clauses() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run("((fn ((a b) c) ((if c + -) a b)) '(1 2) true)", &mut env).unwrap(),
+        RispExp::Integer(3)
+    );
+    assert_eq!(
+        run("((fn [(a b) c] ((if c + -) a b)) '(1 2) false)", &mut env).unwrap(),
+        RispExp::Integer(-1)
+    );
+}
+
+#[test]
+fn every_form_of_a_multi_arity_fn_must_be_a_clause() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run("(fn ([x] x) (x y))", &mut env),
+        Err(RispError::UnexpectedExpr(
+            RispExp::List(Rc::new([
+                RispExp::Symbol("x".to_owned()),
+                RispExp::Symbol("y".to_owned())
+            ])),
+            MALFORMED_FN_CLAUSE.to_owned()
+        ))
+    );
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::run;
+use pretty_assertions::assert_eq;
+use risp_lib::environment::RispEnv;
+use risp_lib::error::{RispError, EXPECTED_ATOM};
+use risp_lib::parser::RispExp;
+
+#[test]
+fn set_updates_the_nearest_binding() {
+    let mut env = RispEnv::default();
+    run("(def x 1)", &mut env).unwrap();
+    assert_eq!(
+        run("(let (y 2) (set! x (+ x y)) (set! y 10) y)", &mut env).unwrap(),
+        RispExp::Integer(10)
+    );
+    assert_eq!(env.get("x").unwrap(), RispExp::Integer(3));
+    assert!(!env.has_interned_var("y"));
+}
+
+#[test]
+fn set_mutates_captured_bindings() {
+    let mut env = RispEnv::default();
+    run(
+        "(def make-counter (fn () (let (n 0) (fn () (set! n (+ n 1))))))",
+        &mut env,
+    )
+    .unwrap();
+    run("(def a (make-counter))", &mut env).unwrap();
+    run("(def b (make-counter))", &mut env).unwrap();
+    run("(a)", &mut env).unwrap();
+    assert_eq!(run("(a)", &mut env).unwrap(), RispExp::Integer(2));
+    assert_eq!(run("(b)", &mut env).unwrap(), RispExp::Integer(1));
+}
+
+#[test]
+fn set_on_unbound_name_is_an_error() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run("(set! nope 1)", &mut env),
+        Err(RispError::UnexpectedSymbol("nope".to_owned()))
+    );
+    assert_eq!(
+        run("(set! 1 1)", &mut env),
+        Err(RispError::MalformedSetExpression)
+    );
+}
+
+#[test]
+fn atoms_are_shared_between_closures() {
+    let mut env = RispEnv::default();
+    run("(def counter (atom 0))", &mut env).unwrap();
+    run("(def inc! (fn () (swap! counter + 1)))", &mut env).unwrap();
+    run("(def peek (fn () (deref counter)))", &mut env).unwrap();
+    run("(inc!)", &mut env).unwrap();
+    assert_eq!(run("(inc!)", &mut env).unwrap(), RispExp::Integer(2));
+    assert_eq!(run("(peek)", &mut env).unwrap(), RispExp::Integer(2));
+    assert_eq!(
+        run("(reset! counter 10)", &mut env).unwrap(),
+        RispExp::Integer(10)
+    );
+    assert_eq!(run("(peek)", &mut env).unwrap(), RispExp::Integer(10));
+}
+
+#[test]
+fn swap_passes_extra_args() {
+    let mut env = RispEnv::default();
+    run("(def a (atom 1))", &mut env).unwrap();
+    assert_eq!(
+        run("(swap! a (fn (x y z) (* x y z)) 2 3)", &mut env).unwrap(),
+        RispExp::Integer(6)
+    );
+}
+
+#[test]
+fn atom_builtins_check_their_args() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run("(deref 1)", &mut env),
+        Err(RispError::TypeError(EXPECTED_ATOM))
+    );
+    assert_eq!(
+        run("(swap! (atom 1) 2)", &mut env),
+        Err(RispError::FirstFormMustBeFunction(RispExp::Integer(2)))
+    );
+}
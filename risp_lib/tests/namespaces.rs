@@ -0,0 +1,144 @@
+mod common;
+
+use common::{run, temp_dir};
+use pretty_assertions::assert_eq;
+use risp_lib::environment::RispEnv;
+use risp_lib::error::RispError;
+use risp_lib::parser::RispExp;
+use risp_lib::tokenizer::Position;
+
+const STR_MODULE: &str = r#"(ns text.str)
+(def join (fn (a b) (+ a b)))
+(def parse (fn (x) "text parse"))
+"#;
+
+const JSON_MODULE: &str = r#"(ns json)
+(def parse (fn (x) "json parse"))
+"#;
+
+#[test]
+fn require_loads_modules_into_their_own_namespace() {
+    let dir = temp_dir(
+        "own",
+        &[("text/str.risp", STR_MODULE), ("json.risp", JSON_MODULE)],
+    );
+    let mut env = RispEnv::default();
+    env.set_search_path(vec![dir]);
+
+    run("(require text.str :as str)", &mut env).unwrap();
+    run("(require json)", &mut env).unwrap();
+    assert_eq!(
+        run("(str/join 1 2)", &mut env).unwrap(),
+        RispExp::Integer(3)
+    );
+    assert_eq!(
+        run("(str/parse 1)", &mut env).unwrap(),
+        RispExp::String("text parse".to_owned())
+    );
+    assert_eq!(
+        run("(json/parse 1)", &mut env).unwrap(),
+        RispExp::String("json parse".to_owned())
+    );
+    assert_eq!(
+        run("(text.str/parse 1)", &mut env).unwrap(),
+        RispExp::String("text parse".to_owned())
+    );
+    // Aliases can be named after builtins too.
+    run("(require json :as do)", &mut env).unwrap();
+    assert_eq!(
+        run("(do/parse 1)", &mut env).unwrap(),
+        RispExp::String("json parse".to_owned())
+    );
+    // The library function of the same name is still there unqualified.
+    assert_eq!(
+        run(r#"(join "-" '(1 2))"#, &mut env).unwrap(),
+        RispExp::String("1-2".to_owned())
+    );
+    // Nothing leaks into the requiring namespace.
+    assert!(!env.has_interned_var("parse"));
+    assert!(!env.has_interned_var("join"));
+}
+
+#[test]
+fn refer_brings_names_in_unqualified() {
+    let dir = temp_dir(
+        "refer",
+        &[("text/str.risp", STR_MODULE), ("json.risp", JSON_MODULE)],
+    );
+    let mut env = RispEnv::default();
+    env.set_search_path(vec![dir]);
+
+    run("(require text.str :refer (join))", &mut env).unwrap();
+    assert_eq!(run("(join 1 2)", &mut env).unwrap(), RispExp::Integer(3));
+    assert!(!env.has_interned_var("parse"));
+
+    run("(require json :refer :all)", &mut env).unwrap();
+    assert_eq!(
+        run("(parse 1)", &mut env).unwrap(),
+        RispExp::String("json parse".to_owned())
+    );
+}
+
+#[test]
+fn modules_see_their_own_definitions_and_requires() {
+    let dir = temp_dir(
+        "deps",
+        &[
+            ("json.risp", JSON_MODULE),
+            (
+                "app.risp",
+                "(ns app)\n(require json :as j)\n(def helper (fn () 41))\n(def run (fn (x) (+ (helper) 1)))\n(def parse-it (fn (x) (j/parse x)))",
+            ),
+        ],
+    );
+    let mut env = RispEnv::default();
+    env.set_search_path(vec![dir]);
+
+    run("(require app)", &mut env).unwrap();
+    assert_eq!(run("(app/run 0)", &mut env).unwrap(), RispExp::Integer(42));
+    assert_eq!(
+        run("(app/parse-it 0)", &mut env).unwrap(),
+        RispExp::String("json parse".to_owned())
+    );
+    // The alias belongs to app, not to the requiring namespace.
+    assert_eq!(
+        run("j/parse", &mut env).unwrap(),
+        RispExp::Symbol("j/parse".to_owned())
+    );
+}
+
+#[test]
+fn ns_switches_the_current_namespace() {
+    let mut env = RispEnv::default();
+    assert_eq!(env.namespace_name(), "user");
+    run("(def x 1)", &mut env).unwrap();
+    run("(ns scratch)", &mut env).unwrap();
+    assert_eq!(env.namespace_name(), "scratch");
+    run("(def x 2)", &mut env).unwrap();
+    assert_eq!(run("(+ x user/x)", &mut env).unwrap(), RispExp::Integer(3));
+    run("(ns user)", &mut env).unwrap();
+    assert_eq!(run("x", &mut env).unwrap(), RispExp::Integer(1));
+}
+
+#[test]
+fn missing_and_broken_modules_are_errors() {
+    let dir = temp_dir("broken", &[("broken.risp", "(def x 1)\n(+ 1 \"a\")")]);
+    let mut env = RispEnv::default();
+    env.set_search_path(vec![dir]);
+
+    assert_eq!(
+        run("(require nope)", &mut env),
+        Err(RispError::ModuleNotFound("nope".to_owned()))
+    );
+    assert!(matches!(
+        run("(require broken)", &mut env),
+        Err(RispError::InForm(Position { line: 2, column: 1 }, e))
+            if matches!(*e, RispError::TypeError(_))
+    ));
+    // A failed load isn't left half registered.
+    assert!(env.namespace("broken").is_none());
+    assert_eq!(
+        run("(require json :as)", &mut env),
+        Err(RispError::MalformedRequireExpression)
+    );
+}
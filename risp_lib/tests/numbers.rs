@@ -0,0 +1,74 @@
+mod common;
+
+use common::{run, show};
+use pretty_assertions::assert_eq;
+use risp_lib::environment::RispEnv;
+use risp_lib::error::RispError;
+use risp_lib::parser::RispExp;
+
+#[test]
+fn factorials_grow_into_bignums() {
+    let mut env = RispEnv::default();
+    run(
+        "(def fact (fn (n) (loop (n n acc 1) (if (< n 2) acc (recur (- n 1) (* acc n))))))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(
+        run("(fact 20)", &mut env).unwrap(),
+        RispExp::Integer(2432902008176640000)
+    );
+    assert_eq!(
+        run("(fact 25)", &mut env).unwrap().to_string(),
+        "15511210043330985984000000"
+    );
+}
+
+#[test]
+fn bignums_shrink_back_to_integers() {
+    assert_eq!(
+        run("(- 9223372036854775808 1)", &mut RispEnv::default()).unwrap(),
+        RispExp::Integer(i64::MAX)
+    );
+    assert_eq!(
+        show("(+ 9223372036854775807 9223372036854775807)"),
+        "18446744073709551614"
+    );
+    assert_eq!(show("(* -9223372036854775808 -1)"), "9223372036854775808");
+}
+
+#[test]
+fn integer_division_is_exact() {
+    assert_eq!(show("(/ 1 3)"), "1/3");
+    assert_eq!(show("(/ 6 4)"), "3/2");
+    assert_eq!(show("(/ 6 3)"), "2");
+    assert_eq!(show("(/ 1 -3)"), "-1/3");
+    assert_eq!(show("(/ 1 2.0)"), "0.5");
+    assert_eq!(
+        run("(/ 1 0)", &mut RispEnv::default()),
+        Err(RispError::DivisionByZero)
+    );
+}
+
+#[test]
+fn ratio_arithmetic() {
+    assert_eq!(show("(+ 1/3 2/3)"), "1");
+    assert_eq!(show("(- 1/2 1/3)"), "1/6");
+    assert_eq!(show("(* 2/3 3/4 4)"), "2");
+    assert_eq!(show("(/ 1/2 1/4)"), "2");
+    assert_eq!(show("(+ 1/2 0.25)"), "0.75");
+    assert_eq!(show("2/4"), "1/2");
+    assert_eq!(
+        run("(/ 1/2 0)", &mut RispEnv::default()),
+        Err(RispError::DivisionByZero)
+    );
+}
+
+#[test]
+fn numbers_compare_across_kinds() {
+    assert_eq!(show("(< 1/3 1/2)"), "#t");
+    assert_eq!(show("(< 1/3 0.3)"), "#f");
+    assert_eq!(show("(> 9223372036854775808 9223372036854775807)"), "#t");
+    assert_eq!(show("(<= 2 5/2)"), "#t");
+    assert_eq!(show("(= 1/2 2/4)"), "#t");
+}
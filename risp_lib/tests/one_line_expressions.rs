@@ -1,99 +1,129 @@
+mod common;
+
+use common::run;
 use pretty_assertions::assert_eq;
 use risp_lib::environment::RispEnv;
-use risp_lib::eval::eval;
 use risp_lib::parser::RispExp;
-use risp_lib::{parser, tokenizer};
 
 #[test]
 fn test_add() {
     let mut env = RispEnv::default();
-    let tokens = tokenizer::tokenize("(+ 1 1)").unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Integer(2));
+    assert_eq!(run("(+ 1 1)", &mut env).unwrap(), RispExp::Integer(2));
 }
 
 #[test]
 fn test_with_negative() {
     let mut env = RispEnv::default();
-    let tokens = tokenizer::tokenize("(+ 2 -1)").unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Integer(1));
+    assert_eq!(run("(+ 2 -1)", &mut env).unwrap(), RispExp::Integer(1));
 }
 
 #[test]
 fn test_sub() {
     let mut env = RispEnv::default();
-    let tokens = tokenizer::tokenize("(- 2 1)").unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Integer(1));
+    assert_eq!(run("(- 2 1)", &mut env).unwrap(), RispExp::Integer(1));
 }
 
 #[test]
 fn test_nesting() {
     let mut env = RispEnv::default();
-    let tokens = tokenizer::tokenize("(- 2 (+ 10 10) 37 (* 10 10))").unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Integer(-155));
+    assert_eq!(
+        run("(- 2 (+ 10 10) 37 (* 10 10))", &mut env).unwrap(),
+        RispExp::Integer(-155)
+    );
 }
 
 #[test]
 fn test_and() {
     let mut env = RispEnv::default();
-    let tokens = tokenizer::tokenize(r#"(and true "wakeupneo")"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Bool(true));
+    assert_eq!(
+        run(r#"(and true "wakeupneo")"#, &mut env).unwrap(),
+        RispExp::Bool(true)
+    );
+
+    assert_eq!(
+        run(r#"(and false "wakeupneo")"#, &mut env).unwrap(),
+        RispExp::Bool(false)
+    );
+}
+
+#[test]
+fn test_and_short_circuits() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(r#"(and false (+ 1 "wakeupneo"))"#, &mut env).unwrap(),
+        RispExp::Bool(false)
+    );
+
+    assert_eq!(
+        run(r#"(and true (def neo "the one"))"#, &mut env).unwrap(),
+        RispExp::Bool(false)
+    );
+    assert!(env.has_interned_var("neo"));
+
+    assert_eq!(
+        run(r#"(and nil (def smith "agent"))"#, &mut env).unwrap(),
+        RispExp::Bool(false)
+    );
+    assert!(!env.has_interned_var("smith"));
+}
+
+#[test]
+fn test_or_short_circuits() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(r#"(or true (+ 1 "wakeupneo"))"#, &mut env).unwrap(),
+        RispExp::Bool(true)
+    );
 
-    let tokens = tokenizer::tokenize(r#"(and false "wakeupneo")"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Bool(false));
+    assert_eq!(
+        run(r#"(or 42 (def smith "agent"))"#, &mut env).unwrap(),
+        RispExp::Bool(true)
+    );
+    assert!(!env.has_interned_var("smith"));
+
+    assert!(run(r#"(or false (+ 1 "wakeupneo"))"#, &mut env).is_err());
 }
+
 #[test]
 fn test_cmp() {
     let mut env = RispEnv::default();
-    let tokens = tokenizer::tokenize(r#"(< 1 100)"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Bool(true));
-    let tokens = tokenizer::tokenize(r#"(< 100 1)"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Bool(false));
-    let tokens = tokenizer::tokenize(r#"(< 100 100)"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Bool(false));
-
-    let tokens = tokenizer::tokenize(r#"(> 1 100)"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Bool(false));
-    let tokens = tokenizer::tokenize(r#"(> 100 1)"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Bool(true));
-    let tokens = tokenizer::tokenize(r#"(> 100 100)"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Bool(false));
-
-    let tokens = tokenizer::tokenize(r#"(<= 1 100)"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Bool(true));
-    let tokens = tokenizer::tokenize(r#"(<= 100 100)"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Bool(true));
-    let tokens = tokenizer::tokenize(r#"(<= 100 1)"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Bool(false));
-
-    let tokens = tokenizer::tokenize(r#"(>= 1 100)"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Bool(false));
-    let tokens = tokenizer::tokenize(r#"(>= 100 100)"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Bool(true));
-    let tokens = tokenizer::tokenize(r#"(>= 100 1)"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Bool(true));
-
-    let tokens = tokenizer::tokenize(r#"(= 100 1)"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Bool(false));
-    let tokens = tokenizer::tokenize(r#"(= 100 100)"#).unwrap();
-    let expr = parser::parse(&tokens).unwrap();
-    assert_eq!(eval(&expr, &mut env).unwrap(), RispExp::Bool(true));
+    assert_eq!(run(r#"(< 1 100)"#, &mut env).unwrap(), RispExp::Bool(true));
+    assert_eq!(run(r#"(< 100 1)"#, &mut env).unwrap(), RispExp::Bool(false));
+    assert_eq!(
+        run(r#"(< 100 100)"#, &mut env).unwrap(),
+        RispExp::Bool(false)
+    );
+
+    assert_eq!(run(r#"(> 1 100)"#, &mut env).unwrap(), RispExp::Bool(false));
+    assert_eq!(run(r#"(> 100 1)"#, &mut env).unwrap(), RispExp::Bool(true));
+    assert_eq!(
+        run(r#"(> 100 100)"#, &mut env).unwrap(),
+        RispExp::Bool(false)
+    );
+
+    assert_eq!(run(r#"(<= 1 100)"#, &mut env).unwrap(), RispExp::Bool(true));
+    assert_eq!(
+        run(r#"(<= 100 100)"#, &mut env).unwrap(),
+        RispExp::Bool(true)
+    );
+    assert_eq!(
+        run(r#"(<= 100 1)"#, &mut env).unwrap(),
+        RispExp::Bool(false)
+    );
+
+    assert_eq!(
+        run(r#"(>= 1 100)"#, &mut env).unwrap(),
+        RispExp::Bool(false)
+    );
+    assert_eq!(
+        run(r#"(>= 100 100)"#, &mut env).unwrap(),
+        RispExp::Bool(true)
+    );
+    assert_eq!(run(r#"(>= 100 1)"#, &mut env).unwrap(), RispExp::Bool(true));
+
+    assert_eq!(run(r#"(= 100 1)"#, &mut env).unwrap(), RispExp::Bool(false));
+    assert_eq!(
+        run(r#"(= 100 100)"#, &mut env).unwrap(),
+        RispExp::Bool(true)
+    );
 }
@@ -0,0 +1,163 @@
+mod common;
+
+use common::{run, run_fresh};
+use pretty_assertions::assert_eq;
+use risp_lib::environment::RispEnv;
+use risp_lib::error::{RispError, MALFORMED_PARAMS, UNKNOWN_KEYWORD_ARG};
+use risp_lib::parser::RispExp;
+use std::rc::Rc;
+
+#[test]
+fn rest_param_collects_extra_args() {
+    let mut env = RispEnv::default();
+    run("(def f (fn (a & more) more))", &mut env).unwrap();
+    assert_eq!(
+        run("(f 1 2 3)", &mut env).unwrap(),
+        RispExp::List(Rc::new([RispExp::Integer(2), RispExp::Integer(3)]))
+    );
+    assert_eq!(run("(f 1)", &mut env).unwrap(), RispExp::List(Rc::new([])));
+    assert!(matches!(
+        run("(f)", &mut env),
+        Err(RispError::ArityMismatch(_))
+    ));
+}
+
+#[test]
+fn optional_params_take_defaults() {
+    let mut env = RispEnv::default();
+    run("(def f (fn (a &opt b (c 10)) (list a b c)))", &mut env).unwrap();
+    run("(def list (fn (& xs) xs))", &mut env).unwrap();
+    assert_eq!(
+        run("(f 1)", &mut env).unwrap(),
+        RispExp::List(Rc::new([
+            RispExp::Integer(1),
+            RispExp::Nil,
+            RispExp::Integer(10)
+        ]))
+    );
+    assert_eq!(
+        run("(f 1 2 3)", &mut env).unwrap(),
+        RispExp::List(Rc::new([
+            RispExp::Integer(1),
+            RispExp::Integer(2),
+            RispExp::Integer(3)
+        ]))
+    );
+    assert!(matches!(
+        run("(f 1 2 3 4)", &mut env),
+        Err(RispError::ArityMismatch(_))
+    ));
+}
+
+#[test]
+fn defaults_see_earlier_params() {
+    let mut env = RispEnv::default();
+    run("(def f (fn (a &opt (b (* a 2))) (+ a b)))", &mut env).unwrap();
+    assert_eq!(run("(f 3)", &mut env).unwrap(), RispExp::Integer(9));
+    assert_eq!(run("(f 3 1)", &mut env).unwrap(), RispExp::Integer(4));
+}
+
+#[test]
+fn optional_and_rest_combine() {
+    let mut env = RispEnv::default();
+    run("(def f (fn (&opt (a 1) & more) a))", &mut env).unwrap();
+    assert_eq!(run("(f)", &mut env).unwrap(), RispExp::Integer(1));
+    run("(def g (fn (&opt (a 1) & more) more))", &mut env).unwrap();
+    assert_eq!(run("(g)", &mut env).unwrap(), RispExp::List(Rc::new([])));
+    assert_eq!(
+        run("(g 5 6 7)", &mut env).unwrap(),
+        RispExp::List(Rc::new([RispExp::Integer(6), RispExp::Integer(7)]))
+    );
+}
+
+#[test]
+fn keyword_params_bind_by_name() {
+    let mut env = RispEnv::default();
+    run(
+        "(def f (fn (a &key b (c (* a 10))) (list a b c)))",
+        &mut env,
+    )
+    .unwrap();
+    run("(def list (fn (& xs) xs))", &mut env).unwrap();
+    assert_eq!(
+        run("(f 1)", &mut env).unwrap(),
+        RispExp::List(Rc::new([
+            RispExp::Integer(1),
+            RispExp::Nil,
+            RispExp::Integer(10)
+        ]))
+    );
+    assert_eq!(
+        run("(f 1 :c 3 :b 2)", &mut env).unwrap(),
+        RispExp::List(Rc::new([
+            RispExp::Integer(1),
+            RispExp::Integer(2),
+            RispExp::Integer(3)
+        ]))
+    );
+    assert!(matches!(
+        run("(f 1 :b)", &mut env),
+        Err(RispError::ArityMismatch(_))
+    ));
+    assert_eq!(
+        run("(f 1 :d 2)", &mut env),
+        Err(RispError::UnexpectedExpr(
+            RispExp::Keyword("d".to_owned()),
+            UNKNOWN_KEYWORD_ARG.to_owned()
+        ))
+    );
+    assert_eq!(
+        run("(f 1 2 3)", &mut env),
+        Err(RispError::UnexpectedExpr(
+            RispExp::Integer(2),
+            UNKNOWN_KEYWORD_ARG.to_owned()
+        ))
+    );
+}
+
+#[test]
+fn variadic_macros() {
+    let mut env = RispEnv::default();
+    run(
+        "(defmacro when (test & body) `(if ~test (do ~@body) nil))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(
+        run("(when (< 1 2) (def x 1) (+ x 1))", &mut env).unwrap(),
+        RispExp::Integer(2)
+    );
+    assert_eq!(
+        run("(when (> 1 2) (+ 1 \"a\"))", &mut env).unwrap(),
+        RispExp::Nil
+    );
+}
+
+#[test]
+fn malformed_params_are_errors() {
+    for (params, bad) in [
+        ("(a & b c)", RispExp::Symbol("&".to_owned())),
+        ("(a &)", RispExp::Symbol("&".to_owned())),
+        ("(&opt a &opt b)", RispExp::Symbol("&opt".to_owned())),
+        ("(&opt a &key b)", RispExp::Symbol("&key".to_owned())),
+        ("(&key a & b)", RispExp::Symbol("&".to_owned())),
+        (
+            "(&key (a))",
+            RispExp::List(Rc::new([RispExp::Symbol("a".to_owned())])),
+        ),
+        (
+            "(a &opt (b 1 2))",
+            RispExp::List(Rc::new([
+                RispExp::Symbol("b".to_owned()),
+                RispExp::Integer(1),
+                RispExp::Integer(2),
+            ])),
+        ),
+        ("(1)", RispExp::Integer(1)),
+    ] {
+        assert_eq!(
+            run_fresh(&format!("(fn {} nil)", params)),
+            Err(RispError::UnexpectedExpr(bad, MALFORMED_PARAMS.to_owned()))
+        );
+    }
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use common::temp_dir;
+use pretty_assertions::assert_eq;
+use risp_lib::environment::RispEnv;
+use risp_lib::error::{RispError, UNEXPECTED_CLOSING_PAREN};
+use risp_lib::eval::{eval_program, load_file};
+use risp_lib::parser::{parse_program, RispExp};
+use risp_lib::tokenizer::{self, Position, RispToken};
+use std::fs;
+
+fn run_program(input: &str, env: &mut RispEnv) -> Result<RispExp, RispError> {
+    let (tokens, positions) = tokenizer::tokenize_with_positions(input)?;
+    let forms = parse_program(&tokens, &positions)?;
+    eval_program(&forms, env)
+}
+
+fn at(line: usize, column: usize) -> Position {
+    Position { line, column }
+}
+
+#[test]
+fn parse_program_reads_every_form() {
+    let (tokens, positions) =
+        tokenizer::tokenize_with_positions("(def a 1)\n  (def b 2) a 3").unwrap();
+    let forms = parse_program(&tokens, &positions).unwrap();
+    assert_eq!(
+        forms
+            .iter()
+            .map(|(_, position)| *position)
+            .collect::<Vec<_>>(),
+        vec![at(1, 1), at(2, 3), at(2, 13), at(2, 15),]
+    );
+    assert_eq!(parse_program(&[], &[]).unwrap(), vec![]);
+}
+
+#[test]
+fn parse_errors_point_at_their_form() {
+    let (tokens, positions) = tokenizer::tokenize_with_positions("(def a 1) 2 )").unwrap();
+    assert_eq!(
+        parse_program(&tokens, &positions),
+        Err(RispError::InForm(
+            at(1, 13),
+            Box::new(RispError::UnexpectedToken(
+                RispToken::RParen,
+                UNEXPECTED_CLOSING_PAREN.to_owned()
+            ))
+        ))
+    );
+}
+
+#[test]
+fn eval_program_runs_forms_in_order() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run_program("(def a 1) (def b (+ a 1)) (+ a b)", &mut env).unwrap(),
+        RispExp::Integer(3)
+    );
+    assert_eq!(run_program("", &mut env).unwrap(), RispExp::Empty);
+}
+
+#[test]
+fn eval_program_stops_at_the_first_error() {
+    let mut env = RispEnv::default();
+    let err = run_program("(def a 1)\n(throw \"stop\") (def b 2)", &mut env).unwrap_err();
+    assert_eq!(
+        err,
+        RispError::InForm(
+            at(2, 1),
+            Box::new(RispError::Thrown("stop".to_owned(), RispExp::Nil))
+        )
+    );
+    assert_eq!(
+        err.to_string(),
+        "Error in the top level form at line 2, column 1: Uncaught exception: stop nil"
+    );
+    assert!(env.has_interned_var("a"));
+    assert!(!env.has_interned_var("b"));
+}
+
+#[test]
+fn load_file_evaluates_a_whole_file() {
+    let dir = temp_dir(
+        "load_file",
+        &[(
+            "square.risp",
+            "(def square (fn (x) (* x x)))\n\n(def nine (square 3))\nnine\n",
+        )],
+    );
+    let path = dir.join("square.risp");
+    let mut env = RispEnv::default();
+    assert_eq!(load_file(&path, &mut env).unwrap(), RispExp::Integer(9));
+    assert_eq!(env.get("nine").unwrap(), RispExp::Integer(9));
+    fs::remove_file(&path).unwrap();
+
+    assert!(matches!(
+        load_file(&path, &mut env),
+        Err(RispError::ModuleLoadError(_, _))
+    ));
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use common::run;
+use pretty_assertions::assert_eq;
+use risp_lib::environment::RispEnv;
+use risp_lib::error::RispError;
+use risp_lib::eval::eval;
+use risp_lib::parser::{RispBuiltinFunction, RispExp, RispFunction};
+use std::rc::Rc;
+
+#[test]
+fn quote_returns_data_unevaluated() {
+    let mut env = RispEnv::default();
+    run("(def a 1)", &mut env).unwrap();
+    assert_eq!(
+        run("'a", &mut env).unwrap(),
+        RispExp::Symbol("a".to_owned())
+    );
+    assert_eq!(
+        run("'(a b c)", &mut env).unwrap(),
+        RispExp::List(Rc::new([
+            RispExp::Symbol("a".to_owned()),
+            RispExp::Symbol("b".to_owned()),
+            RispExp::Symbol("c".to_owned()),
+        ]))
+    );
+    assert_eq!(
+        run("(quote (+ 1 2))", &mut env).unwrap(),
+        RispExp::List(Rc::new([
+            RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
+            RispExp::Integer(1),
+            RispExp::Integer(2),
+        ]))
+    );
+}
+
+#[test]
+fn quoted_code_can_be_evaluated_later() {
+    let mut env = RispEnv::default();
+    run("(def code '(+ 1 2))", &mut env).unwrap();
+    let code = run("code", &mut env).unwrap();
+    assert_eq!(eval(&code, &mut env).unwrap(), RispExp::Integer(3));
+}
+
+#[test]
+fn quasiquote_unquotes() {
+    let mut env = RispEnv::default();
+    run("(def b 2)", &mut env).unwrap();
+    run("(def cs '(3 4))", &mut env).unwrap();
+    assert_eq!(
+        run("`(a ~b ~@cs (+ 1 ~(+ b 1)))", &mut env).unwrap(),
+        RispExp::List(Rc::new([
+            RispExp::Symbol("a".to_owned()),
+            RispExp::Integer(2),
+            RispExp::Integer(3),
+            RispExp::Integer(4),
+            RispExp::List(Rc::new([
+                RispExp::Func(RispFunction::Builtin(RispBuiltinFunction::Plus)),
+                RispExp::Integer(1),
+                RispExp::Integer(3),
+            ])),
+        ]))
+    );
+}
+
+#[test]
+fn unquote_splicing_a_call() {
+    let mut env = RispEnv::default();
+    run("(def cs '(3 4))", &mut env).unwrap();
+    assert_eq!(
+        run("`(a ~@(reverse cs) b)", &mut env).unwrap(),
+        RispExp::List(Rc::new([
+            RispExp::Symbol("a".to_owned()),
+            RispExp::Integer(4),
+            RispExp::Integer(3),
+            RispExp::Symbol("b".to_owned()),
+        ]))
+    );
+}
+
+#[test]
+fn unquote_splicing_needs_a_list() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run("`(a ~@1)", &mut env).unwrap_err(),
+        RispError::TypeError(risp_lib::error::UNQUOTE_SPLICING_EXPECTS_LIST)
+    );
+    assert_eq!(
+        run("`(a ~@nil)", &mut env).unwrap(),
+        RispExp::List(Rc::new([RispExp::Symbol("a".to_owned())]))
+    );
+}
+
+#[test]
+fn unquote_outside_quasiquote_is_an_error() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run("~a", &mut env).unwrap_err(),
+        RispError::UnquoteOutsideQuasiquote("unquote".to_owned())
+    );
+    assert_eq!(
+        run("(unquote-splicing a)", &mut env).unwrap_err(),
+        RispError::UnquoteOutsideQuasiquote("unquote-splicing".to_owned())
+    );
+}
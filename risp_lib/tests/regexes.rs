@@ -0,0 +1,84 @@
+mod common;
+
+use common::{error, show};
+use pretty_assertions::assert_eq;
+use risp_lib::error::{RispError, EXPECTED_REGEX, EXPECTED_STRING};
+use risp_lib::{parser, tokenizer};
+
+#[test]
+fn regex_literals_evaluate_to_themselves() {
+    assert_eq!(show(r#"#"a+b""#), r#"#"a+b""#);
+    assert_eq!(show(r#"(= #"a+" #"a+")"#), "#t");
+    assert_eq!(show(r#"(= #"a+" #"a*")"#), "#f");
+    assert_eq!(show(r#"(:k {#"a+" 1 :k #"a+"})"#), r#"#"a+""#);
+}
+
+#[test]
+fn re_find_gives_the_first_match() {
+    assert_eq!(show(r#"(re-find #"\d+" "ab12cd345")"#), "12");
+    assert_eq!(show(r#"(re-find #"\d+" "abcd")"#), "nil");
+    assert_eq!(
+        show(r#"(re-find #"(\w+)@(\w+)?" "mail me@ now")"#),
+        "[me@, me, nil]"
+    );
+}
+
+#[test]
+fn re_matches_needs_the_whole_string() {
+    assert_eq!(show(r#"(re-matches #"\d+" "123")"#), "123");
+    assert_eq!(show(r#"(re-matches #"\d+" "123a")"#), "nil");
+    // The leftmost match of a|ab is a, but ab matches the whole string.
+    assert_eq!(show(r#"(re-matches #"a|ab" "ab")"#), "ab");
+    assert_eq!(show(r#"(re-matches #"(\d)-(\d)" "1-2")"#), "[1-2, 1, 2]");
+}
+
+#[test]
+fn re_seq_gives_every_match() {
+    assert_eq!(show(r#"(re-seq #"\d+" "a1b22c333")"#), "(1, 22, 333)");
+    assert_eq!(show(r#"(re-seq #"\d+" "abc")"#), "()");
+    assert_eq!(
+        show(r#"(re-seq #"(\w)=(\d)" "a=1;b=2")"#),
+        "([a=1, a, 1], [b=2, b, 2])"
+    );
+}
+
+#[test]
+fn re_replace_replaces_every_match() {
+    assert_eq!(show(r##"(re-replace #"\d" "a1b2" "#")"##), "a#b#");
+    assert_eq!(
+        show(r#"(re-replace #"(\w)=(\d)" "a=1;b=2" "$2=$1")"#),
+        "1=a;2=b"
+    );
+}
+
+#[test]
+fn bad_patterns_are_parse_errors() {
+    match tokenizer::tokenize(r#"(re-find #"a(" "a")"#).map(|tokens| parser::parse(&tokens)) {
+        Ok(Err(RispError::InvalidRegex(pattern, message))) => {
+            assert_eq!(pattern, "a(");
+            assert!(message.contains("unclosed group"), "{}", message);
+        }
+        other => panic!("Expected an invalid regex error, got {:?}", other),
+    }
+}
+
+#[test]
+fn regex_builtins_check_their_args() {
+    assert_eq!(
+        error(r#"(re-find "a" "a")"#),
+        RispError::TypeError(EXPECTED_REGEX)
+    );
+    assert_eq!(
+        error(r#"(re-find #"a" 1)"#),
+        RispError::TypeError(EXPECTED_STRING)
+    );
+    assert!(matches!(
+        error(r#"(re-seq #"a")"#),
+        RispError::ArityMismatch(_)
+    ));
+}
+
+#[test]
+fn regex_functions_can_be_shadowed() {
+    assert_eq!(show("(let (re-seq 1) re-seq)"), "1");
+}
@@ -0,0 +1,155 @@
+mod common;
+
+use common::{error, run, show};
+use pretty_assertions::assert_eq;
+use risp_lib::environment::RispEnv;
+use risp_lib::error::{RispError, EXPECTED_INTEGER, EXPECTED_SEQUENCE, RANGE_STEP_IS_ZERO};
+use risp_lib::parser::RispExp;
+
+#[test]
+fn building_and_taking_apart() {
+    assert_eq!(show("(cons 1 '(2 3))"), "(1, 2, 3)");
+    assert_eq!(show("(cons 1 nil)"), "(1)");
+    assert_eq!(show("(first '(1 2 3))"), "1");
+    assert_eq!(show("(first [])"), "nil");
+    assert_eq!(show("(rest [1 2 3])"), "(2, 3)");
+    assert_eq!(show("(rest '())"), "()");
+    assert_eq!(show("(nth '(1 2 3) 1)"), "2");
+    assert_eq!(show("(nth [1 2 3] 5 :none)"), ":none");
+    assert_eq!(error("(nth '(1 2 3) 3)"), RispError::IndexOutOfBounds(3, 3));
+    assert_eq!(
+        error("(nth '(1 2 3) -1)"),
+        RispError::IndexOutOfBounds(-1, 3)
+    );
+    assert_eq!(
+        error("(nth '(1 2 3) 1.0)"),
+        RispError::TypeError(EXPECTED_INTEGER)
+    );
+}
+
+#[test]
+fn counting() {
+    assert_eq!(show("(count '(1 2 3))"), "3");
+    assert_eq!(show("(count nil)"), "0");
+    assert_eq!(show("(count {:a 1 :b 2})"), "2");
+    assert_eq!(show(r#"(count "héllo")"#), "5");
+    assert_eq!(show("(empty? '())"), "#t");
+    assert_eq!(show("(empty? #{1})"), "#f");
+}
+
+#[test]
+fn every_collection_is_a_sequence() {
+    assert_eq!(show("(first #{:a})"), ":a");
+    assert_eq!(show("(first {:a 1})"), "[:a, 1]");
+    assert_eq!(show(r#"(first "abc")"#), "a");
+    assert_eq!(error("(first 1)"), RispError::TypeError(EXPECTED_SEQUENCE));
+}
+
+#[test]
+fn higher_order_functions_take_builtins_and_fns() {
+    let mut env = RispEnv::default();
+    run("(def square (fn (x) (* x x)))", &mut env).unwrap();
+    assert_eq!(
+        run("(map square '(1 2 3))", &mut env).unwrap().to_string(),
+        "(1, 4, 9)"
+    );
+    assert_eq!(
+        run("(map + [1 2 3] '(10 20))", &mut env)
+            .unwrap()
+            .to_string(),
+        "(11, 22)"
+    );
+    assert_eq!(
+        run("(filter (fn (x) (> x 1)) [1 2 3])", &mut env)
+            .unwrap()
+            .to_string(),
+        "(2, 3)"
+    );
+    assert_eq!(
+        run("(reduce + '(1 2 3 4))", &mut env).unwrap(),
+        RispExp::Integer(10)
+    );
+    assert_eq!(
+        run("(reduce (fn (acc x) (cons x acc)) '() [1 2 3])", &mut env)
+            .unwrap()
+            .to_string(),
+        "(3, 2, 1)"
+    );
+    assert_eq!(
+        run("(map :name [{:name 1} {:age 2}])", &mut env)
+            .unwrap()
+            .to_string(),
+        "(1, nil)"
+    );
+}
+
+#[test]
+fn reduce_edge_cases() {
+    assert_eq!(show("(reduce + '())"), "0");
+    assert_eq!(show("(reduce * 5 '())"), "5");
+    assert_eq!(show("(reduce + '(7))"), "7");
+}
+
+#[test]
+fn non_functions_are_errors() {
+    assert_eq!(
+        error("(map 1 '(1 2))"),
+        RispError::FirstFormMustBeFunction(RispExp::Integer(1))
+    );
+    assert!(matches!(error("(map +)"), RispError::ArityMismatch(_)));
+}
+
+#[test]
+fn concat_and_reverse() {
+    assert_eq!(show("(concat '(1 2) [3] nil #{4})"), "(1, 2, 3, 4)");
+    assert_eq!(show("(concat)"), "()");
+    assert_eq!(show("(reverse [1 2 3])"), "(3, 2, 1)");
+}
+
+#[test]
+fn ranges() {
+    assert_eq!(show("(range 4)"), "(0, 1, 2, 3)");
+    assert_eq!(show("(range 2 5)"), "(2, 3, 4)");
+    assert_eq!(show("(range 10 0 -3)"), "(10, 7, 4, 1)");
+    assert_eq!(show("(range 5 2)"), "()");
+    assert_eq!(
+        show("(range 9223372036854775806 9223372036854775807 5)"),
+        "(9223372036854775806)"
+    );
+    assert_eq!(
+        error("(range 0 5 0)"),
+        RispError::UnexpectedExpr(RispExp::Integer(0), RANGE_STEP_IS_ZERO.to_owned())
+    );
+    assert!(matches!(error("(range)"), RispError::ArityMismatch(_)));
+}
+
+#[test]
+fn sequence_functions_compose() {
+    let mut env = RispEnv::default();
+    run(
+        "(def sum-of-big-squares (fn (n) (reduce + 0 (map (fn (x) (* x x)) (filter (fn (x) (> x 4)) (range n))))))",
+        &mut env,
+    )
+    .unwrap();
+    assert_eq!(
+        run("(sum-of-big-squares 10)", &mut env).unwrap(),
+        RispExp::Integer(25 + 36 + 49 + 64 + 81)
+    );
+}
+
+#[test]
+fn sequence_functions_can_be_shadowed_and_redefined() {
+    assert_eq!(show("(let (first 1) first)"), "1");
+    assert_eq!(show("((fn (count) count) 2)"), "2");
+    assert_eq!(show("(let ((map) '(3)) map)"), "3");
+    let mut env = RispEnv::default();
+    run("(def reverse (fn (xs) :reversed))", &mut env).unwrap();
+    assert_eq!(
+        run("(reverse '(1 2))", &mut env).unwrap(),
+        RispExp::Keyword("reversed".to_owned())
+    );
+    assert_eq!(
+        run("(count (rest '(1 2 3)))", &mut env).unwrap(),
+        RispExp::Integer(2)
+    );
+}
@@ -0,0 +1,164 @@
+mod common;
+
+use common::run_fresh;
+use pretty_assertions::assert_eq;
+use risp_lib::error::{RispError, EXPECTED_STRING};
+use risp_lib::parser::RispExp;
+
+fn string(s: &str) -> RispExp {
+    RispExp::String(s.to_owned())
+}
+
+fn strings(items: &[&str]) -> RispExp {
+    RispExp::List(items.iter().map(|s| string(s)).collect())
+}
+
+#[test]
+fn str_concatenates_text() {
+    assert_eq!(
+        run_fresh(r#"(str "a" 1 :b (first "cd") nil 1/2 "d")"#).unwrap(),
+        string("a1:bc1/2d")
+    );
+    assert_eq!(run_fresh("(str)").unwrap(), string(""));
+    assert_eq!(run_fresh("(str 'a :b 'c/d)").unwrap(), string("a:bc/d"));
+    assert_eq!(run_fresh("(join \",\" '(a b))").unwrap(), string("a,b"));
+}
+
+#[test]
+fn subs_counts_chars() {
+    assert_eq!(run_fresh(r#"(subs "héllo" 1 3)"#).unwrap(), string("él"));
+    assert_eq!(run_fresh(r#"(subs "héllo" 2)"#).unwrap(), string("llo"));
+    assert_eq!(run_fresh(r#"(subs "日本語" 3)"#).unwrap(), string(""));
+    assert_eq!(
+        run_fresh(r#"(subs "héllo" 1 6)"#),
+        Err(RispError::IndexOutOfBounds(6, 5))
+    );
+    assert_eq!(
+        run_fresh(r#"(subs "héllo" 3 2)"#),
+        Err(RispError::StartAfterEnd(3, 2))
+    );
+    assert_eq!(
+        run_fresh(r#"(subs "héllo" -1)"#),
+        Err(RispError::IndexOutOfBounds(-1, 5))
+    );
+}
+
+#[test]
+fn subs_start_after_end_is_an_error() {
+    assert_eq!(
+        run_fresh(r#"(subs "hello" 3 1)"#),
+        Err(RispError::StartAfterEnd(3, 1))
+    );
+    assert_eq!(
+        run_fresh(r#"(subs "hello" 6 1)"#),
+        Err(RispError::IndexOutOfBounds(6, 5))
+    );
+}
+
+#[test]
+fn lengths_and_case() {
+    assert_eq!(
+        run_fresh(r#"(str-len "日本語")"#).unwrap(),
+        RispExp::Integer(3)
+    );
+    assert_eq!(run_fresh(r#"(str-len "")"#).unwrap(), RispExp::Integer(0));
+    assert_eq!(run_fresh(r#"(upper "straße")"#).unwrap(), string("STRASSE"));
+    assert_eq!(run_fresh(r#"(lower "ÀÉÎ")"#).unwrap(), string("àéî"));
+}
+
+#[test]
+fn literals_keep_spaces_and_escapes() {
+    assert_eq!(
+        run_fresh(r#"(split "a  b" " ")"#).unwrap(),
+        strings(&["a", "", "b"])
+    );
+    assert_eq!(
+        run_fresh(r#"(str "say \"hi\"\n" "\u{e9}")"#).unwrap(),
+        string("say \"hi\"\né")
+    );
+}
+
+#[test]
+fn trim_removes_unicode_whitespace() {
+    assert_eq!(
+        run_fresh(r#"(trim "\t hi there\u{3000}\n")"#).unwrap(),
+        string("hi there")
+    );
+}
+
+#[test]
+fn split_and_join() {
+    assert_eq!(
+        run_fresh(r#"(split "a,b,,c" ",")"#).unwrap(),
+        strings(&["a", "b", "", "c"])
+    );
+    assert_eq!(
+        run_fresh(r#"(split "añb" "")"#).unwrap(),
+        strings(&["a", "ñ", "b"])
+    );
+    assert_eq!(
+        run_fresh(r#"(join "-" '("a" 1 :k))"#).unwrap(),
+        string("a-1-:k")
+    );
+    assert_eq!(run_fresh(r#"(join ["x" "y"])"#).unwrap(), string("xy"));
+    assert_eq!(
+        run_fresh(r#"(join "," (split "a,b" ","))"#).unwrap(),
+        string("a,b")
+    );
+}
+
+#[test]
+fn searching() {
+    assert_eq!(
+        run_fresh(r#"(starts-with? "héllo" "hé")"#).unwrap(),
+        RispExp::Bool(true)
+    );
+    assert_eq!(
+        run_fresh(r#"(starts-with? "héllo" "l")"#).unwrap(),
+        RispExp::Bool(false)
+    );
+    assert_eq!(
+        run_fresh(r#"(index-of "日本語の本" "本")"#).unwrap(),
+        RispExp::Integer(1)
+    );
+    assert_eq!(run_fresh(r#"(index-of "abc" "z")"#).unwrap(), RispExp::Nil);
+    assert_eq!(
+        run_fresh(r#"(replace "a.b.c" "." "::")"#).unwrap(),
+        string("a::b::c")
+    );
+}
+
+#[test]
+fn non_strings_are_type_errors() {
+    for input in [
+        "(subs 1 0)",
+        r#"(upper 'a)"#,
+        r#"(split "a" 1)"#,
+        r#"(join 1 '("a"))"#,
+        r#"(index-of nil "a")"#,
+        r#"(replace "a" "a" \b)"#,
+    ] {
+        assert_eq!(
+            run_fresh(input),
+            Err(RispError::TypeError(EXPECTED_STRING)),
+            "{}",
+            input
+        );
+    }
+    assert!(matches!(
+        run_fresh("(trim)"),
+        Err(RispError::ArityMismatch(_))
+    ));
+}
+
+#[test]
+fn string_functions_can_be_shadowed() {
+    assert_eq!(
+        run_fresh("(let (str 1 split 2) (+ str split))").unwrap(),
+        RispExp::Integer(3)
+    );
+    assert_eq!(
+        run_fresh("((fn (join) (join 1)) (fn (x) x))").unwrap(),
+        RispExp::Integer(1)
+    );
+}
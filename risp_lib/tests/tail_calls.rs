@@ -0,0 +1,72 @@
+mod common;
+
+use common::run;
+use risp_lib::environment::RispEnv;
+use risp_lib::parser::RispExp;
+
+#[test]
+fn self_recursive_countdown_runs_in_constant_stack() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(
+            r#"(def countdown (fn (n) (if (= n 0) "done" (countdown (- n 1)))))"#,
+            &mut env
+        )
+        .unwrap(),
+        RispExp::Nil
+    );
+
+    assert_eq!(
+        run(r#"(countdown 1000000)"#, &mut env).unwrap(),
+        RispExp::String("done".to_owned())
+    );
+}
+
+#[test]
+fn accumulating_loop_runs_in_constant_stack() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(
+            r#"(def sum_to (fn (n acc) (if (= n 0) acc (sum_to (- n 1) (+ acc n)))))"#,
+            &mut env
+        )
+        .unwrap(),
+        RispExp::Nil
+    );
+
+    assert_eq!(
+        run(r#"(sum_to 50000 0)"#, &mut env).unwrap(),
+        RispExp::Integer(1250025000)
+    );
+}
+
+#[test]
+fn mutually_recursive_functions_run_in_constant_stack() {
+    let mut env = RispEnv::default();
+    assert_eq!(
+        run(
+            r#"(def is_even (fn (n) (if (= n 0) true (is_odd (- n 1)))))"#,
+            &mut env
+        )
+        .unwrap(),
+        RispExp::Nil
+    );
+    assert_eq!(
+        run(
+            r#"(def is_odd (fn (n) (if (= n 0) false (is_even (- n 1)))))"#,
+            &mut env
+        )
+        .unwrap(),
+        RispExp::Nil
+    );
+
+    assert_eq!(
+        run(r#"(is_even 100000)"#, &mut env).unwrap(),
+        RispExp::Bool(true)
+    );
+
+    assert_eq!(
+        run(r#"(is_odd 100001)"#, &mut env).unwrap(),
+        RispExp::Bool(true)
+    );
+}